@@ -99,7 +99,7 @@ fn main() {
     let (src_rot, dst_rot, gt_rot) = get_rotation_test_data();
 
     let est_rot =
-        LinearRotationSolver::new(max_iteration, tol, noise_bound, c).solve(&src_rot, &dst_rot);
+        LinearRotationSolver::new(max_iteration, tol, noise_bound, c).solve((&src_rot, &dst_rot));
 
     println!("Ground Truth:\n{:?}\n", gt_rot);
     println!("FracGM:\n{:?}\n", est_rot);
@@ -116,13 +116,13 @@ fn main() {
         );
 
         let est_reg = LinearRegistrationSolver::new(max_iteration, tol, noise_bound, c)
-            .solve(&src_reg, &dst_reg);
+            .solve((&src_reg, &dst_reg));
 
         println!("Ground Truth:\n{:?}\n", gt_reg);
         println!("FracGM:\n{:?}\n", est_reg);
     } else {
         let est_reg = LinearRegistrationSolver::new(max_iteration, tol, noise_bound, c)
-            .solve(&src_reg, &dst_reg);
+            .solve((&src_reg, &dst_reg));
 
         println!("Ground Truth:\n{:?}\n", gt_reg);
         println!("FracGM:\n{:?}\n", est_reg);
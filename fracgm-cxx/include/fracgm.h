@@ -11,6 +11,7 @@
 namespace fracgm {
 
 using PointCloud = Eigen::Matrix<double, Eigen::Dynamic, 3, Eigen::RowMajor>;
+using PlanarPointCloud = Eigen::Matrix<double, Eigen::Dynamic, 2, Eigen::RowMajor>;
 
 class LinearRotationSolver {
  public:
@@ -66,6 +67,132 @@ class LinearRegistrationSolver {
   double noise_bound_;
 };
 
+class PlanarRotationSolver {
+ public:
+  PlanarRotationSolver(size_t max_iteration, double tol, double c, double noise_bound)
+      : max_iteration_(max_iteration), tol_(tol), c_(c), noise_bound_(noise_bound) {}
+
+  Eigen::Matrix<double, 2, 2, Eigen::RowMajor> solve(const PlanarPointCloud &pc1, const PlanarPointCloud &pc2) {
+    rust::CBufferF64 buf = rust::fracgm_planar_rotation_solver(pc1.data(), pc1.rows(), pc1.cols(), pc2.data(),
+                                                               pc2.rows(), pc2.cols(), max_iteration_, tol_,
+                                                               noise_bound_, c_);
+    Eigen::Matrix<double, 2, 2, Eigen::RowMajor> result;
+
+    for (int i = 0; i < 2; i++) {
+      for (int j = 0; j < 2; j++) {
+        result(i, j) = buf.data[i * 2 + j];
+      }
+    }
+
+    rust::free_f64_buf(buf);
+    return result;
+  }
+
+ protected:
+  size_t max_iteration_;
+  double tol_;
+  double c_;
+  double noise_bound_;
+};
+
+class PlanarRegistrationSolver {
+ public:
+  PlanarRegistrationSolver(size_t max_iteration, double tol, double c, double noise_bound)
+      : max_iteration_(max_iteration), tol_(tol), c_(c), noise_bound_(noise_bound) {}
+
+  Eigen::Matrix<double, 3, 3, Eigen::RowMajor> solve(const PlanarPointCloud &pc1, const PlanarPointCloud &pc2) {
+    rust::CBufferF64 buf = rust::fracgm_planar_registration_solver(pc1.data(), pc1.rows(), pc1.cols(), pc2.data(),
+                                                                   pc2.rows(), pc2.cols(), max_iteration_, tol_,
+                                                                   noise_bound_, c_);
+    Eigen::Matrix<double, 3, 3, Eigen::RowMajor> result;
+
+    for (int i = 0; i < 3; i++) {
+      for (int j = 0; j < 3; j++) {
+        result(i, j) = buf.data[i * 3 + j];
+      }
+    }
+
+    rust::free_f64_buf(buf);
+    return result;
+  }
+
+ protected:
+  size_t max_iteration_;
+  double tol_;
+  double c_;
+  double noise_bound_;
+};
+
+class PointToPlaneRegistrationSolver {
+ public:
+  PointToPlaneRegistrationSolver(size_t max_iteration, double tol, double c, double noise_bound)
+      : max_iteration_(max_iteration), tol_(tol), c_(c), noise_bound_(noise_bound) {}
+
+  Eigen::Matrix<double, 4, 4, Eigen::RowMajor> solve(const PointCloud &pc1, const PointCloud &pc2,
+                                                     const PointCloud &normals) {
+    rust::CBufferF64 buf = rust::fracgm_point_to_plane_registration_solver(
+        pc1.data(), pc1.rows(), pc1.cols(), pc2.data(), pc2.rows(), pc2.cols(), normals.data(), normals.rows(),
+        normals.cols(), max_iteration_, tol_, noise_bound_, c_);
+    Eigen::Matrix<double, 4, 4, Eigen::RowMajor> result;
+
+    for (int i = 0; i < 4; i++) {
+      for (int j = 0; j < 4; j++) {
+        result(i, j) = buf.data[i * 4 + j];
+      }
+    }
+
+    rust::free_f64_buf(buf);
+    return result;
+  }
+
+ protected:
+  size_t max_iteration_;
+  double tol_;
+  double c_;
+  double noise_bound_;
+};
+
+class PnPSolver {
+ public:
+  PnPSolver(size_t max_iteration, double tol, double c, double noise_bound)
+      : max_iteration_(max_iteration), tol_(tol), c_(c), noise_bound_(noise_bound) {}
+
+  Eigen::Matrix<double, 4, 4, Eigen::RowMajor> solve(const PointCloud &bearings, const PointCloud &points) {
+    rust::CBufferF64 buf =
+        rust::fracgm_pnp_solver(bearings.data(), bearings.rows(), bearings.cols(), points.data(), points.rows(),
+                                points.cols(), max_iteration_, tol_, noise_bound_, c_);
+    return to_pose(buf);
+  }
+
+  Eigen::Matrix<double, 4, 4, Eigen::RowMajor> solve(const PlanarPointCloud &pixels,
+                                                     const Eigen::Matrix<double, 3, 3, Eigen::RowMajor> &intrinsics,
+                                                     const PointCloud &points) {
+    rust::CBufferF64 buf = rust::fracgm_pnp_pixels_solver(pixels.data(), pixels.rows(), pixels.cols(),
+                                                          intrinsics.data(), points.data(), points.rows(),
+                                                          points.cols(), max_iteration_, tol_, noise_bound_, c_);
+    return to_pose(buf);
+  }
+
+ protected:
+  Eigen::Matrix<double, 4, 4, Eigen::RowMajor> to_pose(rust::CBufferF64 buf) {
+    Eigen::Matrix<double, 4, 4, Eigen::RowMajor> result;
+
+    for (int i = 0; i < 4; i++) {
+      for (int j = 0; j < 4; j++) {
+        result(i, j) = buf.data[i * 4 + j];
+      }
+    }
+
+    rust::free_f64_buf(buf);
+    return result;
+  }
+
+  size_t max_iteration_;
+  double tol_;
+  double c_;
+  double noise_bound_;
+};
+
 std::vector<size_t> max_clique_inlier_selection(const PointCloud &pc1, const PointCloud &pc2, double noise_bound,
                                                 double pmc_timeout) {
   rust::CBufferUSize buf = rust::fracgm_max_clique_inlier_selection(pc1.data(), pc1.rows(), pc1.cols(), pc2.data(),
@@ -80,4 +207,18 @@ std::vector<size_t> max_clique_inlier_selection(const PointCloud &pc1, const Poi
   return indices;
 }
 
+std::vector<size_t> max_clique_inlier_selection(const PlanarPointCloud &pc1, const PlanarPointCloud &pc2,
+                                                double noise_bound, double pmc_timeout) {
+  rust::CBufferUSize buf = rust::fracgm_max_clique_inlier_selection(pc1.data(), pc1.rows(), pc1.cols(), pc2.data(),
+                                                                    pc2.rows(), pc2.cols(), noise_bound, pmc_timeout);
+  std::vector<size_t> indices;
+
+  for (size_t i = 0; i < buf.len; i++) {
+    indices.push_back(buf.data[i]);
+  }
+
+  rust::free_usize_buf(buf);
+  return indices;
+}
+
 }  // namespace fracgm
\ No newline at end of file
@@ -23,6 +23,29 @@ CBufferUSize fracgm_max_clique_inlier_selection(const double *pc1, size_t pc1_n_
                                                 const double *pc2, size_t pc2_n_rows, size_t pc2_n_cols,
                                                 double noise_bound, double pmc_timeout);
 
+CBufferF64 fracgm_planar_registration_solver(const double *pc1, size_t pc1_n_rows, size_t pc1_n_cols,
+                                             const double *pc2, size_t pc2_n_rows, size_t pc2_n_cols,
+                                             size_t max_iteration, double tol, double noise_bound, double c);
+
+CBufferF64 fracgm_planar_rotation_solver(const double *pc1, size_t pc1_n_rows, size_t pc1_n_cols, const double *pc2,
+                                         size_t pc2_n_rows, size_t pc2_n_cols, size_t max_iteration, double tol,
+                                         double noise_bound, double c);
+
+CBufferF64 fracgm_pnp_pixels_solver(const double *pixels, size_t pixels_n_rows, size_t pixels_n_cols,
+                                    const double *intrinsics, const double *points, size_t points_n_rows,
+                                    size_t points_n_cols, size_t max_iteration, double tol, double noise_bound,
+                                    double c);
+
+CBufferF64 fracgm_pnp_solver(const double *bearings, size_t bearings_n_rows, size_t bearings_n_cols,
+                             const double *points, size_t points_n_rows, size_t points_n_cols, size_t max_iteration,
+                             double tol, double noise_bound, double c);
+
+CBufferF64 fracgm_point_to_plane_registration_solver(const double *pc1, size_t pc1_n_rows, size_t pc1_n_cols,
+                                                     const double *pc2, size_t pc2_n_rows, size_t pc2_n_cols,
+                                                     const double *normals, size_t normals_n_rows,
+                                                     size_t normals_n_cols, size_t max_iteration, double tol,
+                                                     double noise_bound, double c);
+
 CBufferF64 fracgm_registration_solver(const double *pc1, size_t pc1_n_rows, size_t pc1_n_cols, const double *pc2,
                                       size_t pc2_n_rows, size_t pc2_n_cols, size_t max_iteration, double tol,
                                       double noise_bound, double c);
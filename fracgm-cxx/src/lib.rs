@@ -4,7 +4,7 @@
 
 mod array_helper;
 use fracgm::{
-    mcis::max_clique_inlier_selection, registration, rotation, solver::GemanMcclureSolver,
+    mcis::max_clique_inlier_selection, pnp, registration, rotation, solver::GemanMcclureSolver,
 };
 use libc;
 
@@ -33,7 +33,35 @@ pub extern "C" fn fracgm_rotation_solver(
     let pc1 = array_helper::to_array2(pc1, pc1_n_rows, pc1_n_cols);
     let pc2 = array_helper::to_array2(pc2, pc2_n_rows, pc2_n_cols);
 
-    let rot = GemanMcclureSolver::solve(&solver, &pc1, &pc2);
+    let rot = GemanMcclureSolver::solve(&solver, (&pc1, &pc2));
+
+    array_helper::to_f64_buf(&rot)
+}
+
+#[no_mangle]
+pub extern "C" fn fracgm_planar_rotation_solver(
+    pc1: *const libc::c_double,
+    pc1_n_rows: libc::size_t,
+    pc1_n_cols: libc::size_t,
+    pc2: *const libc::c_double,
+    pc2_n_rows: libc::size_t,
+    pc2_n_cols: libc::size_t,
+    max_iteration: libc::size_t,
+    tol: libc::c_double,
+    noise_bound: libc::c_double,
+    c: libc::c_double,
+) -> array_helper::CBufferF64 {
+    let solver = rotation::PlanarSolver::new(
+        max_iteration,
+        tol,
+        Some(noise_bound) as Option<f64>,
+        Some(c) as Option<f64>,
+    );
+
+    let pc1 = array_helper::to_array2(pc1, pc1_n_rows, pc1_n_cols);
+    let pc2 = array_helper::to_array2(pc2, pc2_n_rows, pc2_n_cols);
+
+    let rot = GemanMcclureSolver::solve(&solver, (&pc1, &pc2));
 
     array_helper::to_f64_buf(&rot)
 }
@@ -61,7 +89,125 @@ pub extern "C" fn fracgm_registration_solver(
     let pc1 = array_helper::to_array2(pc1, pc1_n_rows, pc1_n_cols);
     let pc2 = array_helper::to_array2(pc2, pc2_n_rows, pc2_n_cols);
 
-    let solution = GemanMcclureSolver::solve(&solver, &pc1, &pc2);
+    let solution = GemanMcclureSolver::solve(&solver, (&pc1, &pc2));
+
+    array_helper::to_f64_buf(&solution)
+}
+
+#[no_mangle]
+pub extern "C" fn fracgm_planar_registration_solver(
+    pc1: *const libc::c_double,
+    pc1_n_rows: libc::size_t,
+    pc1_n_cols: libc::size_t,
+    pc2: *const libc::c_double,
+    pc2_n_rows: libc::size_t,
+    pc2_n_cols: libc::size_t,
+    max_iteration: libc::size_t,
+    tol: libc::c_double,
+    noise_bound: libc::c_double,
+    c: libc::c_double,
+) -> array_helper::CBufferF64 {
+    let solver = registration::PlanarSolver::new(
+        max_iteration,
+        tol,
+        Some(noise_bound) as Option<f64>,
+        Some(c) as Option<f64>,
+    );
+
+    let pc1 = array_helper::to_array2(pc1, pc1_n_rows, pc1_n_cols);
+    let pc2 = array_helper::to_array2(pc2, pc2_n_rows, pc2_n_cols);
+
+    let solution = GemanMcclureSolver::solve(&solver, (&pc1, &pc2));
+
+    array_helper::to_f64_buf(&solution)
+}
+
+#[no_mangle]
+pub extern "C" fn fracgm_point_to_plane_registration_solver(
+    pc1: *const libc::c_double,
+    pc1_n_rows: libc::size_t,
+    pc1_n_cols: libc::size_t,
+    pc2: *const libc::c_double,
+    pc2_n_rows: libc::size_t,
+    pc2_n_cols: libc::size_t,
+    normals: *const libc::c_double,
+    normals_n_rows: libc::size_t,
+    normals_n_cols: libc::size_t,
+    max_iteration: libc::size_t,
+    tol: libc::c_double,
+    noise_bound: libc::c_double,
+    c: libc::c_double,
+) -> array_helper::CBufferF64 {
+    let solver = registration::PointToPlaneSolver::new(
+        max_iteration,
+        tol,
+        Some(noise_bound) as Option<f64>,
+        Some(c) as Option<f64>,
+    );
+
+    let pc1 = array_helper::to_array2(pc1, pc1_n_rows, pc1_n_cols);
+    let pc2 = array_helper::to_array2(pc2, pc2_n_rows, pc2_n_cols);
+    let normals = array_helper::to_array2(normals, normals_n_rows, normals_n_cols);
+
+    let solution = GemanMcclureSolver::solve(&solver, (&pc1, &pc2, &normals));
+
+    array_helper::to_f64_buf(&solution)
+}
+
+#[no_mangle]
+pub extern "C" fn fracgm_pnp_solver(
+    bearings: *const libc::c_double,
+    bearings_n_rows: libc::size_t,
+    bearings_n_cols: libc::size_t,
+    points: *const libc::c_double,
+    points_n_rows: libc::size_t,
+    points_n_cols: libc::size_t,
+    max_iteration: libc::size_t,
+    tol: libc::c_double,
+    noise_bound: libc::c_double,
+    c: libc::c_double,
+) -> array_helper::CBufferF64 {
+    let solver = pnp::LinearSolver::new(
+        max_iteration,
+        tol,
+        Some(noise_bound) as Option<f64>,
+        Some(c) as Option<f64>,
+    );
+
+    let bearings = array_helper::to_array2(bearings, bearings_n_rows, bearings_n_cols);
+    let points = array_helper::to_array2(points, points_n_rows, points_n_cols);
+
+    let solution = GemanMcclureSolver::solve(&solver, (&bearings, &points));
+
+    array_helper::to_f64_buf(&solution)
+}
+
+#[no_mangle]
+pub extern "C" fn fracgm_pnp_pixels_solver(
+    pixels: *const libc::c_double,
+    pixels_n_rows: libc::size_t,
+    pixels_n_cols: libc::size_t,
+    intrinsics: *const libc::c_double,
+    points: *const libc::c_double,
+    points_n_rows: libc::size_t,
+    points_n_cols: libc::size_t,
+    max_iteration: libc::size_t,
+    tol: libc::c_double,
+    noise_bound: libc::c_double,
+    c: libc::c_double,
+) -> array_helper::CBufferF64 {
+    let solver = pnp::LinearSolver::new(
+        max_iteration,
+        tol,
+        Some(noise_bound) as Option<f64>,
+        Some(c) as Option<f64>,
+    );
+
+    let pixels = array_helper::to_array2(pixels, pixels_n_rows, pixels_n_cols);
+    let intrinsics = array_helper::to_array2(intrinsics, 3, 3);
+    let points = array_helper::to_array2(points, points_n_rows, points_n_cols);
+
+    let solution = solver.solve_pixels(&pixels, &intrinsics, &points);
 
     array_helper::to_f64_buf(&solution)
 }
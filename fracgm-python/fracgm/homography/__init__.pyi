@@ -0,0 +1,50 @@
+# Copyright 2024 the FracGM authors. All rights reserved.
+# Use of this source code is governed by a BSD-style
+# license that can be found in the LICENSE file.
+
+import typing as T
+
+import numpy.typing as npt
+
+class LinearHomographySolver:
+    def __init__(
+        self,
+        max_iter: int,
+        tol: float,
+        noise_bound: T.Optional[float],
+        c: T.Optional[float],
+    ):
+        """Initialize the Geman-McClure linear solver for homography estimation.
+
+        Args:
+            max_iter (int): Maximum number of iterations.
+            tol (float): Tolerance for the stopping criterion.
+            noise_bound (T.Optional[float]): Noise bound of the DLT residuals of the Hartley-normalized points (default: 0.1).
+            c (T.Optional[float]): Gemam McClure parameter (default: 1.0).
+        """
+        ...
+
+    def solve(self, pts1: npt.ArrayLike, pts2: npt.ArrayLike) -> npt.ArrayLike:
+        """Solve the homography estimation problem given corresponding image points.
+
+        Args:
+            pts1 (npt.ArrayLike): Image points of the first view (N x 2).
+            pts2 (npt.ArrayLike): Image points of the second view (N x 2).
+
+        Returns:
+            npt.ArrayLike: Homography (3 x 3) that maps pts1 to pts2, normalized with H[2, 2] = 1.
+        """
+        ...
+
+    def inliers(self, pts1: npt.ArrayLike, pts2: npt.ArrayLike, homography: npt.ArrayLike) -> T.List[bool]:
+        """Classify the correspondences into inliers and outliers with the given homography.
+
+        Args:
+            pts1 (npt.ArrayLike): Image points of the first view (N x 2).
+            pts2 (npt.ArrayLike): Image points of the second view (N x 2).
+            homography (npt.ArrayLike): Homography (3 x 3).
+
+        Returns:
+            T.List[bool]: Whether each correspondence is an inlier.
+        """
+        ...
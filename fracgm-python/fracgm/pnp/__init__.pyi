@@ -0,0 +1,91 @@
+# Copyright 2024 the FracGM authors. All rights reserved.
+# Use of this source code is governed by a BSD-style
+# license that can be found in the LICENSE file.
+
+import typing as T
+
+import numpy.typing as npt
+
+from fracgm import Diagnostic
+
+class LinearPnPSolver:
+    def __init__(
+        self,
+        max_iter: int,
+        tol: float,
+        noise_bound: T.Optional[float],
+        c: T.Optional[float],
+    ):
+        """Initialize the Geman-McClure linear solver for absolute camera pose problem.
+
+        Args:
+            max_iter (int): Maximum number of iterations.
+            tol (float): Tolerance for the stopping criterion.
+            noise_bound (T.Optional[float]): Noise bound of the object-space residuals in the units of the 3D points (default: 0.1).
+            c (T.Optional[float]): Gemam McClure parameter (default: 1.0).
+        """
+        ...
+
+    def solve(self, bearings: npt.ArrayLike, points: npt.ArrayLike) -> npt.ArrayLike:
+        """Solve the absolute camera pose problem given bearing vectors and 3D points.
+
+        Args:
+            bearings (npt.ArrayLike): Bearing vectors of the camera (N x 3).
+            points (npt.ArrayLike): Corresponding 3D points (N x 3).
+
+        Returns:
+            npt.ArrayLike: SE(3) matrix that maps the points into the camera frame.
+        """
+        ...
+
+    def solve_pixels(
+        self, pixels: npt.ArrayLike, intrinsics: npt.ArrayLike, points: npt.ArrayLike
+    ) -> npt.ArrayLike:
+        """Solve the absolute camera pose problem given pixel coordinates and 3D points.
+
+        Args:
+            pixels (npt.ArrayLike): Pixel coordinates (N x 2).
+            intrinsics (npt.ArrayLike): Intrinsic matrix of the camera (3 x 3).
+            points (npt.ArrayLike): Corresponding 3D points (N x 3).
+
+        Returns:
+            npt.ArrayLike: SE(3) matrix that maps the points into the camera frame.
+        """
+        ...
+
+    def solve_with_diagnostic(self, bearings: npt.ArrayLike, points: npt.ArrayLike) -> Diagnostic:
+        """Solve the absolute camera pose problem given bearing vectors and 3D points with diagnostic.
+
+        Args:
+            bearings (npt.ArrayLike): Bearing vectors of the camera (N x 3).
+            points (npt.ArrayLike): Corresponding 3D points (N x 3).
+
+        Returns:
+            Diagnostic: Solution with diagnostic.
+        """
+        ...
+
+    def inliers(self, bearings: npt.ArrayLike, points: npt.ArrayLike, pose: npt.ArrayLike) -> T.List[bool]:
+        """Classify the correspondences into inliers and outliers at the given pose.
+
+        Args:
+            bearings (npt.ArrayLike): Bearing vectors of the camera (N x 3).
+            points (npt.ArrayLike): Corresponding 3D points (N x 3).
+            pose (npt.ArrayLike): SE(3) matrix.
+
+        Returns:
+            T.List[bool]: Whether each correspondence is an inlier.
+        """
+        ...
+
+def pixels_to_bearings(pixels: npt.ArrayLike, intrinsics: npt.ArrayLike) -> npt.ArrayLike:
+    """Convert pixel coordinates to unit bearing vectors.
+
+    Args:
+        pixels (npt.ArrayLike): Pixel coordinates (N x 2).
+        intrinsics (npt.ArrayLike): Intrinsic matrix of the camera (3 x 3).
+
+    Returns:
+        npt.ArrayLike: Unit bearing vectors (N x 3).
+    """
+    ...
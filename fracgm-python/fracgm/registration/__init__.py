@@ -5,6 +5,8 @@
 from fracgm.fracgm import (
     DecoupledRegistrationSolver,  # noqa: F401
     LinearRegistrationSolver,  # noqa: F401
+    PlanarRegistrationSolver,  # noqa: F401
+    PointToPlaneRegistrationSolver,  # noqa: F401
     TIMPolicy,  # noqa: F401
     max_clique_inlier_selection,  # noqa: F401
 )
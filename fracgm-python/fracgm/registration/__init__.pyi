@@ -50,6 +50,92 @@ class LinearRegistrationSolver:
         """
         ...
 
+class PlanarRegistrationSolver:
+    def __init__(
+        self,
+        max_iter: int,
+        tol: float,
+        noise_bound: T.Optional[float],
+        c: T.Optional[float],
+    ):
+        """Initialize the Geman-McClure linear solver for planar registration problem.
+
+        Args:
+            max_iter (int): Maximum number of iterations.
+            tol (float): Tolerance for the stopping criterion.
+            noise_bound (T.Optional[float]): Noise bound of the data (default: 0.1).
+            c (T.Optional[float]): Gemam McClure parameter (default: 1.0).
+        """
+        ...
+
+    def solve(self, A: npt.ArrayLike, B: npt.ArrayLike) -> npt.ArrayLike:
+        """Solve the planar registration problem given two planar point clouds.
+
+        Args:
+            A (npt.ArrayLike): Planar pointcloud A.
+            B (npt.ArrayLike): Planar pointcloud B.
+
+        Returns:
+            npt.ArrayLike: SE(2) matrix.
+        """
+
+    def solve_with_diagnostic(self, A: npt.ArrayLike, B: npt.ArrayLike) -> Diagnostic:
+        """Solve the planar registration problem given two planar point clouds and return diagnostic.
+
+        Args:
+            A (npt.ArrayLike): Planar pointcloud A.
+            B (npt.ArrayLike): Planar pointcloud B.
+
+        Returns:
+            Diagnostic: Solution with diagnostic.
+        """
+        ...
+
+class PointToPlaneRegistrationSolver:
+    def __init__(
+        self,
+        max_iter: int,
+        tol: float,
+        noise_bound: T.Optional[float],
+        c: T.Optional[float],
+    ):
+        """Initialize the Geman-McClure linear solver for point-to-plane registration problem.
+
+        Args:
+            max_iter (int): Maximum number of iterations.
+            tol (float): Tolerance for the stopping criterion.
+            noise_bound (T.Optional[float]): Noise bound of the data (default: 0.1).
+            c (T.Optional[float]): Gemam McClure parameter (default: 1.0).
+        """
+        ...
+
+    def solve(self, A: npt.ArrayLike, B: npt.ArrayLike, N: npt.ArrayLike) -> npt.ArrayLike:
+        """Solve the point-to-plane registration problem given two point clouds and the normals of B.
+
+        Args:
+            A (npt.ArrayLike): Pointcloud A.
+            B (npt.ArrayLike): Pointcloud B.
+            N (npt.ArrayLike): Unit normals of pointcloud B.
+
+        Returns:
+            npt.ArrayLike: SE(3) matrix.
+        """
+
+    def solve_with_diagnostic(
+        self, A: npt.ArrayLike, B: npt.ArrayLike, N: npt.ArrayLike
+    ) -> Diagnostic:
+        """Solve the point-to-plane registration problem given two point clouds and the normals of B and return diagnostic.
+
+        Args:
+            A (npt.ArrayLike): Pointcloud A.
+            B (npt.ArrayLike): Pointcloud B.
+            N (npt.ArrayLike): Unit normals of pointcloud B.
+
+        Returns:
+            Diagnostic: Solution with diagnostic.
+        """
+        ...
+
 class TIMPolicy(Enum):
     COMPLETE = 0
     CHAIN = 1
@@ -80,15 +166,24 @@ class DecoupledRegistrationSolver:
         """
         ...
 
+    def set_estimate_scaling(self, estimate_scaling: bool):
+        """Set whether to estimate the scale, i.e., solve Sim(3) instead of SE(3).
+
+        Args:
+            estimate_scaling (bool): Whether to estimate the scale (default: False).
+        """
+        ...
+
     def solve(self, A: npt.ArrayLike, B: npt.ArrayLike) -> npt.ArrayLike:
-        """Solve the registration problem given two point clouds.
+        """Solve the registration problem given two point clouds (3D or planar).
 
         Args:
             A (npt.ArrayLike): Pointcloud A.
             B (npt.ArrayLike): Pointcloud B.
 
         Returns:
-            npt.ArrayLike: SE(3) matrix.
+            npt.ArrayLike: SE(3) matrix, or SE(2) matrix for planar point clouds.
+                The rotation block is scaled if the scale is estimated.
         """
 
 def max_clique_inlier_selection(
@@ -96,6 +191,7 @@ def max_clique_inlier_selection(
 ) -> npt.ArrayLike:
     """
     Find inlier correspondences using the Maximum clique inlier selection (MCIS) algorithm.
+    Both 3D and planar point clouds are supported.
 
     Args:
         A (npt.ArrayLike): Pointcloud A.
@@ -0,0 +1,50 @@
+# Copyright 2024 the FracGM authors. All rights reserved.
+# Use of this source code is governed by a BSD-style
+# license that can be found in the LICENSE file.
+
+import typing as T
+
+import numpy.typing as npt
+
+class LinearRegressionSolver:
+    def __init__(
+        self,
+        max_iter: int,
+        tol: float,
+        noise_bound: T.Optional[float],
+        c: T.Optional[float],
+    ):
+        """Initialize the Geman-McClure linear solver for robust linear regression.
+
+        Args:
+            max_iter (int): Maximum number of iterations.
+            tol (float): Tolerance for the stopping criterion.
+            noise_bound (T.Optional[float]): Noise bound of the norm of the residuals, in the units of the targets (default: 0.1).
+            c (T.Optional[float]): Gemam McClure parameter (default: 1.0).
+        """
+        ...
+
+    def set_intercept(self, intercept: bool):
+        """Set whether the model has an intercept (default: True).
+
+        Args:
+            intercept (bool): Whether the model has an intercept.
+        """
+        ...
+
+    def fit(
+        self, x: npt.ArrayLike, y: npt.ArrayLike
+    ) -> T.Tuple[npt.ArrayLike, npt.ArrayLike, T.List[float], T.List[bool]]:
+        """Fit the linear model y = x @ coefficients + intercept to the samples.
+
+        Args:
+            x (npt.ArrayLike): Features (N x P).
+            y (npt.ArrayLike): Targets (N,) or (N x Q).
+
+        Returns:
+            T.Tuple[npt.ArrayLike, npt.ArrayLike, T.List[float], T.List[bool]]: Coefficients (P x Q), intercept (Q,), Geman-McClure weight of each sample, and whether each sample is an inlier.
+
+        Raises:
+            ValueError: If the targets are neither 1D nor 2D.
+        """
+        ...
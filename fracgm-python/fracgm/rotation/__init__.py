@@ -2,4 +2,7 @@
 # Use of this source code is governed by a BSD-style
 # license that can be found in the LICENSE file.
 
-from fracgm.fracgm import LinearRotationSolver  # noqa: F401
+from fracgm.fracgm import (
+    LinearRotationSolver,  # noqa: F401
+    PlanarRotationSolver,  # noqa: F401
+)
@@ -48,3 +48,44 @@ class LinearRotationSolver:
             Diagnostic: Solution with diagnostic.
         """
         ...
+
+class PlanarRotationSolver:
+    def __init__(
+        self,
+        max_iter: int,
+        tol: float,
+        noise_bound: T.Optional[float],
+        c: T.Optional[float],
+    ):
+        """Initialize the Geman-McClure linear solver for planar rotation problem.
+
+        Args:
+            max_iter (int): Maximum number of iterations.
+            tol (float): Tolerance for the stopping criterion.
+            noise_bound (T.Optional[float]): Noise bound of the data (default: 0.1).
+            c (T.Optional[float]): Gemam McClure parameter (default: 1.0).
+        """
+        ...
+
+    def solve(self, A: npt.ArrayLike, B: npt.ArrayLike) -> npt.ArrayLike:
+        """Solve the planar rotation problem given two planar point clouds.
+
+        Args:
+            A (npt.ArrayLike): Planar pointcloud A.
+            B (npt.ArrayLike): Planar pointcloud B.
+
+        Returns:
+            npt.ArrayLike: SO(2) matrix.
+        """
+
+    def solve_with_diagnostic(self, A: npt.ArrayLike, B: npt.ArrayLike) -> Diagnostic:
+        """Solve the planar rotation problem given two planar point clouds and return diagnostic.
+
+        Args:
+            A (npt.ArrayLike): Planar pointcloud A.
+            B (npt.ArrayLike): Planar pointcloud B.
+
+        Returns:
+            Diagnostic: Solution with diagnostic.
+        """
+        ...
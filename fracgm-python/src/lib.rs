@@ -2,11 +2,13 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use numpy::{IntoPyArray, PyArray2, PyReadonlyArray2};
+use numpy::ndarray::Ix2;
+use numpy::{IntoPyArray, PyArray1, PyArray2, PyReadonlyArray2, PyReadonlyArrayDyn};
+use pyo3::exceptions::PyValueError;
 use pyo3::{pyclass, pyfunction, pymethods, pymodule, Bound, PyResult, Python};
 
 use fracgm::{
-    mcis, registration, rotation,
+    homography, mcis, pnp, registration, regression, rotation,
     solver::{self, GemanMcclureSolver, GemanMcclureSolverDiagnostic},
     translation,
 };
@@ -109,7 +111,7 @@ impl LinearRotationSolver {
         let pc1 = pc1.as_array().to_owned();
         let pc2 = pc2.as_array().to_owned();
 
-        let rot = GemanMcclureSolver::solve(&self.0, &pc1, &pc2);
+        let rot = GemanMcclureSolver::solve(&self.0, (&pc1, &pc2));
 
         rot.into_pyarray_bound(py)
     }
@@ -122,7 +124,50 @@ impl LinearRotationSolver {
         let pc1 = pc1.as_array().to_owned();
         let pc2 = pc2.as_array().to_owned();
 
-        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, &pc1, &pc2);
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&pc1, &pc2));
+
+        Diagnostic::from(&diagnostic)
+    }
+}
+
+#[pyclass]
+pub struct PlanarRotationSolver(rotation::PlanarSolver);
+
+#[pymethods]
+impl PlanarRotationSolver {
+    #[new]
+    fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        PlanarRotationSolver(rotation::PlanarSolver::new(
+            max_iteration,
+            tol,
+            noise_bound,
+            c,
+        ))
+    }
+
+    unsafe fn solve<'py>(
+        &self,
+        py: Python<'py>,
+        pc1: PyReadonlyArray2<'py, f64>,
+        pc2: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray2<f64>> {
+        let pc1 = pc1.as_array().to_owned();
+        let pc2 = pc2.as_array().to_owned();
+
+        let rot = GemanMcclureSolver::solve(&self.0, (&pc1, &pc2));
+
+        rot.into_pyarray_bound(py)
+    }
+
+    unsafe fn solve_with_diagnostic<'py>(
+        &self,
+        pc1: PyReadonlyArray2<'py, f64>,
+        pc2: PyReadonlyArray2<'py, f64>,
+    ) -> Diagnostic {
+        let pc1 = pc1.as_array().to_owned();
+        let pc2 = pc2.as_array().to_owned();
+
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&pc1, &pc2));
 
         Diagnostic::from(&diagnostic)
     }
@@ -152,7 +197,95 @@ impl LinearRegistrationSolver {
         let pc1 = pc1.as_array().to_owned();
         let pc2 = pc2.as_array().to_owned();
 
-        let mat = GemanMcclureSolver::solve(&self.0, &pc1, &pc2);
+        let mat = GemanMcclureSolver::solve(&self.0, (&pc1, &pc2));
+
+        mat.into_pyarray_bound(py)
+    }
+
+    unsafe fn solve_with_diagnostic<'py>(
+        &self,
+        pc1: PyReadonlyArray2<'py, f64>,
+        pc2: PyReadonlyArray2<'py, f64>,
+    ) -> Diagnostic {
+        let pc1 = pc1.as_array().to_owned();
+        let pc2 = pc2.as_array().to_owned();
+
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&pc1, &pc2));
+
+        Diagnostic::from(&diagnostic)
+    }
+}
+
+#[pyclass]
+pub struct PlanarRegistrationSolver(registration::PlanarSolver);
+
+#[pymethods]
+impl PlanarRegistrationSolver {
+    #[new]
+    fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        PlanarRegistrationSolver(registration::PlanarSolver::new(
+            max_iteration,
+            tol,
+            noise_bound,
+            c,
+        ))
+    }
+
+    unsafe fn solve<'py>(
+        &self,
+        py: Python<'py>,
+        pc1: PyReadonlyArray2<'py, f64>,
+        pc2: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray2<f64>> {
+        let pc1 = pc1.as_array().to_owned();
+        let pc2 = pc2.as_array().to_owned();
+
+        let mat = GemanMcclureSolver::solve(&self.0, (&pc1, &pc2));
+
+        mat.into_pyarray_bound(py)
+    }
+
+    unsafe fn solve_with_diagnostic<'py>(
+        &self,
+        pc1: PyReadonlyArray2<'py, f64>,
+        pc2: PyReadonlyArray2<'py, f64>,
+    ) -> Diagnostic {
+        let pc1 = pc1.as_array().to_owned();
+        let pc2 = pc2.as_array().to_owned();
+
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&pc1, &pc2));
+
+        Diagnostic::from(&diagnostic)
+    }
+}
+
+#[pyclass]
+pub struct PointToPlaneRegistrationSolver(registration::PointToPlaneSolver);
+
+#[pymethods]
+impl PointToPlaneRegistrationSolver {
+    #[new]
+    fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        PointToPlaneRegistrationSolver(registration::PointToPlaneSolver::new(
+            max_iteration,
+            tol,
+            noise_bound,
+            c,
+        ))
+    }
+
+    unsafe fn solve<'py>(
+        &self,
+        py: Python<'py>,
+        pc1: PyReadonlyArray2<'py, f64>,
+        pc2: PyReadonlyArray2<'py, f64>,
+        normals: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray2<f64>> {
+        let pc1 = pc1.as_array().to_owned();
+        let pc2 = pc2.as_array().to_owned();
+        let normals = normals.as_array().to_owned();
+
+        let mat = GemanMcclureSolver::solve(&self.0, (&pc1, &pc2, &normals));
 
         mat.into_pyarray_bound(py)
     }
@@ -161,14 +294,187 @@ impl LinearRegistrationSolver {
         &self,
         pc1: PyReadonlyArray2<'py, f64>,
         pc2: PyReadonlyArray2<'py, f64>,
+        normals: PyReadonlyArray2<'py, f64>,
     ) -> Diagnostic {
         let pc1 = pc1.as_array().to_owned();
         let pc2 = pc2.as_array().to_owned();
+        let normals = normals.as_array().to_owned();
+
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&pc1, &pc2, &normals));
+
+        Diagnostic::from(&diagnostic)
+    }
+}
+
+#[pyclass]
+pub struct LinearPnPSolver(pnp::LinearSolver);
+
+#[pymethods]
+impl LinearPnPSolver {
+    #[new]
+    fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        LinearPnPSolver(pnp::LinearSolver::new(max_iteration, tol, noise_bound, c))
+    }
+
+    unsafe fn solve<'py>(
+        &self,
+        py: Python<'py>,
+        bearings: PyReadonlyArray2<'py, f64>,
+        points: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray2<f64>> {
+        let bearings = bearings.as_array().to_owned();
+        let points = points.as_array().to_owned();
+
+        let mat = GemanMcclureSolver::solve(&self.0, (&bearings, &points));
+
+        mat.into_pyarray_bound(py)
+    }
+
+    unsafe fn solve_pixels<'py>(
+        &self,
+        py: Python<'py>,
+        pixels: PyReadonlyArray2<'py, f64>,
+        intrinsics: PyReadonlyArray2<'py, f64>,
+        points: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray2<f64>> {
+        let pixels = pixels.as_array().to_owned();
+        let intrinsics = intrinsics.as_array().to_owned();
+        let points = points.as_array().to_owned();
+
+        let mat = self.0.solve_pixels(&pixels, &intrinsics, &points);
+
+        mat.into_pyarray_bound(py)
+    }
 
-        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, &pc1, &pc2);
+    unsafe fn solve_with_diagnostic<'py>(
+        &self,
+        bearings: PyReadonlyArray2<'py, f64>,
+        points: PyReadonlyArray2<'py, f64>,
+    ) -> Diagnostic {
+        let bearings = bearings.as_array().to_owned();
+        let points = points.as_array().to_owned();
+
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&bearings, &points));
 
         Diagnostic::from(&diagnostic)
     }
+
+    unsafe fn inliers<'py>(
+        &self,
+        bearings: PyReadonlyArray2<'py, f64>,
+        points: PyReadonlyArray2<'py, f64>,
+        pose: PyReadonlyArray2<'py, f64>,
+    ) -> Vec<bool> {
+        let bearings = bearings.as_array().to_owned();
+        let points = points.as_array().to_owned();
+        let pose = pose.as_array().to_owned();
+
+        self.0.inliers(&bearings, &points, &pose)
+    }
+}
+
+#[pyfunction]
+pub fn pixels_to_bearings<'py>(
+    py: Python<'py>,
+    pixels: PyReadonlyArray2<'py, f64>,
+    intrinsics: PyReadonlyArray2<'py, f64>,
+) -> Bound<'py, PyArray2<f64>> {
+    let pixels = pixels.as_array().to_owned();
+    let intrinsics = intrinsics.as_array().to_owned();
+
+    pnp::utils::pixels_to_bearings(&pixels, &intrinsics).into_pyarray_bound(py)
+}
+
+#[pyclass]
+pub struct LinearHomographySolver(homography::LinearSolver);
+
+#[pymethods]
+impl LinearHomographySolver {
+    #[new]
+    fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        LinearHomographySolver(homography::LinearSolver::new(
+            max_iteration,
+            tol,
+            noise_bound,
+            c,
+        ))
+    }
+
+    unsafe fn solve<'py>(
+        &self,
+        py: Python<'py>,
+        pts1: PyReadonlyArray2<'py, f64>,
+        pts2: PyReadonlyArray2<'py, f64>,
+    ) -> Bound<'py, PyArray2<f64>> {
+        let pts1 = pts1.as_array().to_owned();
+        let pts2 = pts2.as_array().to_owned();
+
+        let mat = self.0.solve_points(&pts1, &pts2);
+
+        mat.into_pyarray_bound(py)
+    }
+
+    unsafe fn inliers<'py>(
+        &self,
+        pts1: PyReadonlyArray2<'py, f64>,
+        pts2: PyReadonlyArray2<'py, f64>,
+        homography: PyReadonlyArray2<'py, f64>,
+    ) -> Vec<bool> {
+        let pts1 = pts1.as_array().to_owned();
+        let pts2 = pts2.as_array().to_owned();
+        let homography = homography.as_array().to_owned();
+
+        self.0.inliers(&pts1, &pts2, &homography)
+    }
+}
+
+#[pyclass]
+pub struct LinearRegressionSolver(regression::LinearSolver);
+
+#[pymethods]
+impl LinearRegressionSolver {
+    #[new]
+    fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        LinearRegressionSolver(regression::LinearSolver::new(
+            max_iteration,
+            tol,
+            noise_bound,
+            c,
+        ))
+    }
+
+    unsafe fn set_intercept<'py>(&mut self, intercept: bool) {
+        self.0.set_intercept(intercept);
+    }
+
+    unsafe fn fit<'py>(
+        &self,
+        py: Python<'py>,
+        x: PyReadonlyArray2<'py, f64>,
+        y: PyReadonlyArrayDyn<'py, f64>,
+    ) -> PyResult<(
+        Bound<'py, PyArray2<f64>>,
+        Bound<'py, PyArray1<f64>>,
+        Vec<f64>,
+        Vec<bool>,
+    )> {
+        let x = x.as_array().to_owned();
+        let y = y.as_array();
+        let y = match y.ndim() {
+            1 => y.to_owned().into_shape((y.len(), 1)).unwrap(),
+            2 => y.to_owned().into_dimensionality::<Ix2>().unwrap(),
+            _ => return Err(PyValueError::new_err("Targets must be a 1D or 2D array")),
+        };
+
+        let fit = self.0.fit(&x, &y);
+
+        Ok((
+            fit.model.coefficients.into_pyarray_bound(py),
+            fit.model.intercept.into_pyarray_bound(py),
+            fit.weights,
+            fit.inliers,
+        ))
+    }
 }
 
 #[pyclass]
@@ -195,7 +501,7 @@ impl LinearTranslationSolver {
         let pc1 = pc1.as_array().to_owned();
         let pc2 = pc2.as_array().to_owned();
 
-        let mat = GemanMcclureSolver::solve(&self.0, &pc1, &pc2);
+        let mat = GemanMcclureSolver::solve(&self.0, (&pc1, &pc2));
 
         mat.into_pyarray_bound(py)
     }
@@ -208,7 +514,7 @@ impl LinearTranslationSolver {
         let pc1 = pc1.as_array().to_owned();
         let pc2 = pc2.as_array().to_owned();
 
-        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, &pc1, &pc2);
+        let diagnostic = GemanMcclureSolverDiagnostic::solve(&self.0, (&pc1, &pc2));
 
         Diagnostic::from(&diagnostic)
     }
@@ -247,6 +553,10 @@ impl DecoupledRegistrationSolver {
         }
     }
 
+    unsafe fn set_estimate_scaling<'py>(&mut self, estimate_scaling: bool) {
+        self.0.set_estimate_scaling(estimate_scaling);
+    }
+
     unsafe fn solve<'py>(
         &self,
         py: Python<'py>,
@@ -289,9 +599,30 @@ mod py_module {
     #[pymodule_export]
     use LinearRotationSolver;
 
+    #[pymodule_export]
+    use PlanarRotationSolver;
+
     #[pymodule_export]
     use LinearRegistrationSolver;
 
+    #[pymodule_export]
+    use PlanarRegistrationSolver;
+
+    #[pymodule_export]
+    use PointToPlaneRegistrationSolver;
+
+    #[pymodule_export]
+    use LinearPnPSolver;
+
+    #[pymodule_export]
+    use pixels_to_bearings;
+
+    #[pymodule_export]
+    use LinearHomographySolver;
+
+    #[pymodule_export]
+    use LinearRegressionSolver;
+
     #[pymodule_export]
     use LinearTranslationSolver;
 
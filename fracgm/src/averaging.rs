@@ -0,0 +1,13 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solvers of averaging problems over a graph,
+//! whose dimension grows with the number of nodes. Their terms are built on
+//! [`crate::solver::R2Sparse`], so that each term only stores the blocks of
+//! the nodes of its edge.
+
+pub mod rotation;
+pub mod utils;
+
+pub use rotation::RotationSolver;
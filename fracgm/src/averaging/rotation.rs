@@ -0,0 +1,171 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::Array2;
+
+use crate::averaging::utils as avg_utils;
+use crate::manifold::{self, Manifold};
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sparse,
+};
+use crate::sparse::{self, CsrMatrix};
+use crate::utils;
+
+/// FracGM-based rotation averaging solver with linear (naive) relaxation.
+///
+/// Given the relative rotations $R_{ij}$ of the edges $(i, j)$ of a view
+/// graph, it estimates the absolute rotations $R_k$ of the nodes with the
+/// chordal residuals $\|R_j - R_{ij} R_i\|_F$. The gauge freedom is removed by
+/// fixing the rotation of the anchor node to the identity, so the lifted
+/// variable stacks $\mathrm{vec}(R_k)$ of the other nodes and the homogeneous
+/// coordinate. The view graph must be connected.
+///
+/// Unlike the single rotation case, the linear relaxation admits degenerate
+/// solutions that shrink the rotations away from the anchor, which makes the
+/// chordal residuals of all the edges small. The solution of each subproblem
+/// is therefore projected onto SO(3) node by node, as in the iteratively
+/// reweighted least squares methods of rotation averaging. The subproblems
+/// are assembled and solved with the sparse backend of [`crate::sparse`].
+///
+/// Since the iterates are projected, they are not the solutions of the
+/// subproblems, and the convergence guarantee of FracGM does not apply. The
+/// solver is a FracGM-weighted heuristic, whose $\psi$ vanishes at a fixed
+/// point but may not converge in general.
+pub struct RotationSolver {
+    /// The number of nodes of the view graph.
+    pub n_nodes: usize,
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    /// The node whose rotation is fixed to the identity (default: 0).
+    pub anchor: usize,
+}
+
+impl RotationSolver {
+    pub fn new(
+        n_nodes: usize,
+        max_iteration: usize,
+        tol: f64,
+        noise_bound: Option<f64>,
+        c: Option<f64>,
+    ) -> Self {
+        assert!(n_nodes > 1, "View graph must have at least two nodes");
+
+        Self {
+            n_nodes,
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+            anchor: 0,
+        }
+    }
+
+    pub fn set_anchor(&mut self, anchor: usize) {
+        assert!(anchor < self.n_nodes, "Anchor must be a node of the graph");
+        self.anchor = anchor;
+    }
+
+    /// Computes the Geman-McClure weight of each edge at the given rotations,
+    /// which is close to 1 for inliers and vanishes for outliers.
+    ///
+    /// # Arguments
+    ///
+    /// - `edges` - The edges $(i, j, R_{ij})$.
+    /// - `rotations` - The absolute rotations of the nodes.
+    ///
+    /// # Returns
+    ///
+    /// The weight of each edge.
+    pub fn edge_weights(
+        &self,
+        edges: &[(usize, usize, Array2<f64>)],
+        rotations: &Vec<Array2<f64>>,
+    ) -> Vec<f64> {
+        let mut terms = self.compute_terms(&edges);
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(rotations));
+
+        utils::compute_weights(&terms, self.c())
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sparse> for RotationSolver {
+    type Input<'a> = &'a [(usize, usize, Array2<f64>)];
+    type Output = Vec<Array2<f64>>;
+
+    fn dim(&self) -> usize {
+        9 * (self.n_nodes - 1) + 1
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, rotations: &Vec<Array2<f64>>) -> Array2<f64> {
+        avg_utils::rotations_to_vec(rotations, self.anchor)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Vec<Array2<f64>> {
+        avg_utils::vec_to_rotations(vec, self.n_nodes, self.anchor)
+    }
+    fn project(&self, rotations: &Vec<Array2<f64>>) -> Vec<Array2<f64>> {
+        rotations
+            .iter()
+            .map(|rot| manifold::SO3.project(rot))
+            .collect()
+    }
+
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let vec = utils::solve_homogeneous(mat);
+
+        self.mat_to_vec(&self.project(&self.vec_to_mat(&vec)))
+    }
+
+    fn use_sparse(&self) -> bool {
+        true
+    }
+
+    fn solve_x_sparse(&self, mat: &CsrMatrix, vec: &Array2<f64>) -> Array2<f64> {
+        let vec = sparse::solve_homogeneous(mat, Some(vec), sparse::CG_TOL, 10 * self.dim());
+
+        self.mat_to_vec(&self.project(&self.vec_to_mat(&vec)))
+    }
+
+    fn check_input(&self, edges: &Self::Input<'_>) {
+        avg_utils::check_edges(edges, self.n_nodes, (3, 3));
+    }
+
+    fn compute_terms(&self, edges: &Self::Input<'_>) -> Vec<Fractional<R2Sparse>> {
+        edges
+            .iter()
+            .map(|(i, j, rot_ij)| {
+                avg_utils::compute_rotation_term(
+                    (*i, *j, rot_ij),
+                    self.anchor,
+                    self.dim(),
+                    self.noise_bound,
+                    self.c(),
+                )
+            })
+            .collect()
+    }
+
+    fn compute_initial_guess(&self, edges: &Self::Input<'_>) -> Vec<Array2<f64>> {
+        avg_utils::compute_spectral_initial_guess(edges, self.n_nodes, self.anchor)
+    }
+}
+
+impl GemanMcclureSolver<R2Sparse> for RotationSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sparse> for RotationSolver {}
@@ -0,0 +1,228 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::manifold::{self, Manifold};
+use crate::rotation::utils as rot_utils;
+use crate::solver::{Fractional, R2Sparse};
+use crate::sparse::{self, CsrMatrix};
+
+/// The shift of the connection Laplacian relative to its largest diagonal
+/// entry in the spectral initial guess.
+const SPECTRAL_SHIFT: f64 = 1e-6;
+/// The maximum number of inverse iterations of the spectral initial guess.
+const SPECTRAL_MAX_ITERATION: usize = 100;
+/// The tolerance of the change of the eigenvectors between two inverse
+/// iterations.
+const SPECTRAL_TOL: f64 = 1e-9;
+
+/// Gets the offset of the block of `node` in the lifted variable, where the
+/// blocks of size `block` are stacked in the order of the nodes without the
+/// anchor.
+///
+/// # Returns
+///
+/// The offset of the block, or `None` if `node` is the anchor.
+pub fn block_offset(node: usize, anchor: usize, block: usize) -> Option<usize> {
+    match node.cmp(&anchor) {
+        std::cmp::Ordering::Less => Some(node * block),
+        std::cmp::Ordering::Equal => None,
+        std::cmp::Ordering::Greater => Some((node - 1) * block),
+    }
+}
+
+/// Checks if the edges are valid for a graph with `n_nodes` nodes and edge
+/// measurements of shape `shape`.
+///
+/// # Panics
+///
+/// Panics if an edge is a self-loop, refers to a missing node, or has a
+/// measurement of a wrong shape.
+pub fn check_edges(edges: &[(usize, usize, Array2<f64>)], n_nodes: usize, shape: (usize, usize)) {
+    for (i, j, measurement) in edges.iter() {
+        assert!(
+            *i < n_nodes && *j < n_nodes,
+            "Edge ({}, {}) refers to a missing node",
+            i,
+            j
+        );
+        assert!(i != j, "Edge ({}, {}) must not be a self-loop", i, j);
+        assert!(
+            measurement.dim() == shape,
+            "Edge measurements must be {}x{} matrices",
+            shape.0,
+            shape.1
+        );
+    }
+}
+
+/// Computes the Geman-McClure term of the chordal residual $R_j - R_{ij} R_i$
+/// of an edge in the lifted variable that stacks $\mathrm{vec}(R_k)$ of the
+/// nodes without the anchor, whose rotation is fixed to the identity, and the
+/// homogeneous coordinate.
+pub fn compute_rotation_term(
+    (i, j, rot_ij): (usize, usize, &Array2<f64>),
+    anchor: usize,
+    dim: usize,
+    noise_bound: f64,
+    c: f64,
+) -> Fractional<R2Sparse> {
+    // $\mathrm{vec}(R_{ij} R_i) = (I \otimes R_{ij}) \mathrm{vec}(R_i)$.
+    let mat_i = kron(&Array2::<f64>::eye(3), rot_ij).mapv(|x| -x);
+    let mat_j = Array2::<f64>::eye(9);
+
+    let mut constant = Array1::<f64>::zeros(9);
+    let mut blocks = Vec::new();
+    for (node, mat) in [(i, mat_i), (j, mat_j)] {
+        match block_offset(node, anchor, 9) {
+            Some(offset) => blocks.push((offset, mat)),
+            None => {
+                constant += &mat.dot(&rot_utils::rot_mat_to_vec(&Array2::eye(3)).slice(s![0..9, 0]))
+            }
+        }
+    }
+
+    let k = 9 * blocks.len() + 1;
+    let mut mat_n = Array2::<f64>::zeros((9, k));
+    let mut indices = Vec::with_capacity(k);
+    for (b, (offset, mat)) in blocks.iter().enumerate() {
+        mat_n.slice_mut(s![.., 9 * b..9 * (b + 1)]).assign(mat);
+        indices.extend(*offset..*offset + 9);
+    }
+    mat_n.slice_mut(s![.., k - 1]).assign(&constant);
+    indices.push(dim - 1);
+
+    let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+    Fractional::new(R2Sparse::new(mat_m, indices), c)
+}
+
+/// Stacks the rotations of the nodes without the anchor into the lifted
+/// variable.
+pub fn rotations_to_vec(rotations: &[Array2<f64>], anchor: usize) -> Array2<f64> {
+    let dim = 9 * (rotations.len() - 1) + 1;
+
+    let mut vec = Array2::<f64>::ones((dim, 1));
+    for (node, rot) in rotations.iter().enumerate() {
+        if let Some(offset) = block_offset(node, anchor, 9) {
+            vec.slice_mut(s![offset..offset + 9, ..])
+                .assign(&rot_utils::rot_mat_to_vec(rot).slice(s![0..9, ..]));
+        }
+    }
+
+    vec
+}
+
+/// Unstacks the lifted variable into the rotations of all the nodes, where
+/// the rotation of the anchor is the identity.
+pub fn vec_to_rotations(vec: &Array2<f64>, n_nodes: usize, anchor: usize) -> Vec<Array2<f64>> {
+    (0..n_nodes)
+        .map(|node| match block_offset(node, anchor, 9) {
+            Some(offset) => {
+                rot_utils::rot_vec_to_mat(&vec.slice(s![offset..offset + 9, ..]).to_owned())
+            }
+            None => Array2::eye(3),
+        })
+        .collect()
+}
+
+/// Computes the spectral initial guess of rotation averaging. The stacked
+/// rotations $X = [R_1; \dots; R_n]$ satisfy $R_j - R_{ij} R_i = 0$ for all
+/// the edges, so the eigenvectors of the three smallest eigenvalues of the
+/// connection Laplacian $\sum (e_j^\top \otimes I - e_i^\top \otimes
+/// R_{ij})^\top (e_j^\top \otimes I - e_i^\top \otimes R_{ij})$ recover $X$
+/// up to a common $Q \in O(3)$, which is removed with the anchor. Unlike the
+/// anchored least squares solution, it does not shrink the rotations far from
+/// the anchor.
+///
+/// The Laplacian is assembled in CSR format, and the eigenvectors are
+/// computed with subspace inverse iteration of the slightly shifted Laplacian,
+/// whose linear systems are solved with the sparse backend of
+/// [`crate::sparse`].
+pub fn compute_spectral_initial_guess(
+    edges: &[(usize, usize, Array2<f64>)],
+    n_nodes: usize,
+    anchor: usize,
+) -> Vec<Array2<f64>> {
+    let dim = 3 * n_nodes;
+
+    let mut triplets = Vec::with_capacity(36 * edges.len() + dim);
+    for (i, j, rot_ij) in edges.iter() {
+        let blocks = [
+            (*i, *i, rot_ij.t().dot(rot_ij)),
+            (*j, *j, Array2::<f64>::eye(3)),
+            (*j, *i, -rot_ij),
+            (*i, *j, -&rot_ij.t()),
+        ];
+        for (row, col, block) in blocks.iter() {
+            for ((r, c), value) in block.indexed_iter() {
+                triplets.push((3 * row + r, 3 * col + c, *value));
+            }
+        }
+    }
+    let laplacian = CsrMatrix::from_triplets(dim, triplets.clone());
+
+    // The shift keeps the Laplacian positive definite, and is small enough for
+    // the inverse iteration to separate the null space.
+    let shift = SPECTRAL_SHIFT * laplacian.diag().fold(0.0, |a: f64, &b| a.max(b));
+    triplets.extend((0..dim).map(|k| (k, k, shift)));
+    let shifted = CsrMatrix::from_triplets(dim, triplets);
+
+    let mut stacked = orthonormalize(&Array2::from_shape_fn((dim, 3), |(r, c)| {
+        if r % 3 == c {
+            1.0
+        } else {
+            0.0
+        }
+    }));
+    for _ in 0..SPECTRAL_MAX_ITERATION {
+        let mut next = Array2::<f64>::zeros((dim, 3));
+        for k in 0..3 {
+            let column = stacked.column(k).to_owned();
+            next.column_mut(k).assign(&sparse::solve(
+                &shifted,
+                &column,
+                Some(&column),
+                sparse::CG_TOL,
+                10 * dim,
+            ));
+        }
+        let next = orthonormalize(&next);
+
+        let change = &next - &stacked.dot(&stacked.t().dot(&next));
+        stacked = next;
+        if change.iter().map(|x| x * x).sum::<f64>().sqrt() < SPECTRAL_TOL {
+            break;
+        }
+    }
+
+    let anchor_block = stacked.slice(s![3 * anchor..3 * anchor + 3, ..]);
+
+    (0..n_nodes)
+        .map(|node| {
+            let block = stacked.slice(s![3 * node..3 * node + 3, ..]);
+            manifold::SO3.project(&block.dot(&anchor_block.t()))
+        })
+        .collect()
+}
+
+/// Orthonormalizes the columns of a matrix with the modified Gram-Schmidt
+/// process.
+fn orthonormalize(mat: &Array2<f64>) -> Array2<f64> {
+    let mut mat = mat.clone();
+    for k in 0..mat.ncols() {
+        for l in 0..k {
+            let proj = mat.column(l).dot(&mat.column(k));
+            let prev = mat.column(l).to_owned();
+            mat.column_mut(k).scaled_add(-proj, &prev);
+        }
+        let norm = mat.column(k).dot(&mat.column(k)).sqrt();
+        mat.column_mut(k).mapv_inplace(|x| x / norm);
+    }
+
+    mat
+}
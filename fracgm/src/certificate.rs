@@ -0,0 +1,183 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains a global optimality certifier for solutions returned
+//! by the FracGM solvers.
+//!
+//! At a converged iterate $(\beta, \mu)$, FracGM solves the weighted
+//! subproblem $\min_x x^\top A x$ with $A = \sum_i \mu_i (F_i - \beta_i H_i)$
+//! over the feasible set. Under the conditions of Proposition 3 of the paper,
+//! the solution $x^\star$ is a global optimum of the Geman-McClure problem if
+//! (i) $(\beta, \mu)$ is consistent with $x^\star$, i.e., the norm of $\psi$
+//! vanishes, and (ii) $x^\star$ is a global optimum of the weighted
+//! subproblem. The second condition is verified with the Lagrangian (SDP) dual
+//! of the subproblem with redundant quadratic constraints, whose optimal value
+//! is a lower bound of the subproblem.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::sdp;
+use crate::solver::{FractionalProgrammingMaterials, R2};
+
+/// A trait for a FracGM solver whose feasible set is described by quadratic
+/// equality constraints $x^\top A_i x = b_i$ on the lifted variable.
+pub trait Certifiable<R: R2>: FractionalProgrammingMaterials<R> {
+    /// Get the quadratic constraints of the feasible set.
+    ///
+    /// # Returns
+    ///
+    /// The constraint matrices $A_i$ and the right-hand side $b$.
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>);
+}
+
+/// The global optimality certificate of a FracGM solution.
+#[derive(Clone)]
+pub struct Certificate {
+    /// The objective value $\sum_i \mu_i (f_i(x^\star) - \beta_i h_i(x^\star))$
+    /// of the weighted subproblem.
+    pub objective: f64,
+    /// The lower bound of the weighted subproblem given by the SDP dual.
+    pub lower_bound: f64,
+    /// The suboptimality gap normalized by $1 + \|A\|_F$.
+    pub suboptimality_gap: f64,
+    /// The norm of the vector $\psi$ evaluated at the solution.
+    pub psi_norm: f64,
+    /// Whether the SDP dual is solved to the required accuracy.
+    pub dual_converged: bool,
+    /// Whether the solution is certified to be globally optimal.
+    pub certified: bool,
+}
+
+/// A certifier of global optimality for FracGM solutions.
+pub struct Certifier {
+    /// The tolerance of the normalized suboptimality gap (default: 1e-5).
+    pub gap_tol: f64,
+    /// The tolerance of the norm of $\psi$ (default: 1e-6).
+    pub psi_tol: f64,
+    /// The SDP solver for the dual problem.
+    pub sdp_solver: sdp::Solver,
+}
+
+impl Default for Certifier {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl Certifier {
+    pub fn new(gap_tol: Option<f64>, psi_tol: Option<f64>) -> Self {
+        Self {
+            gap_tol: gap_tol.unwrap_or(1e-5),
+            psi_tol: psi_tol.unwrap_or(1e-6),
+            sdp_solver: sdp::Solver::default(),
+        }
+    }
+
+    /// Certify a solution with the converged auxiliary variables. The norm of
+    /// $\psi$ measures whether $(\beta, \mu)$ is consistent with the solution.
+    ///
+    /// # Arguments
+    ///
+    /// - `solver` - The solver that produced the solution.
+    /// - `input` - The measurements.
+    /// - `solution` - The (projected) solution.
+    /// - `beta` - The converged auxiliary variable $\beta$.
+    /// - `mu` - The converged auxiliary variable $\mu$.
+    ///
+    /// # Returns
+    ///
+    /// The global optimality certificate.
+    pub fn certify<R: R2, S: Certifiable<R>>(
+        &self,
+        solver: &S,
+        input: S::Input<'_>,
+        solution: &S::Output,
+        beta: &[f64],
+        mu: &[f64],
+    ) -> Certificate {
+        self.certify_impl(solver, input, solution, beta, mu, true)
+    }
+
+    /// Certify a solution, e.g., the result of
+    /// [`crate::rotation::LinearSolver`] or
+    /// [`crate::registration::LinearSolver`]. The auxiliary variables are
+    /// computed from the solution itself, so that $\psi$ vanishes by
+    /// construction and is not checked; only the optimality of the solution
+    /// for the weighted subproblem is certified. Use [`Certifier::certify`]
+    /// with the final $(\beta, \mu)$ of the solver, e.g., the last iteration
+    /// of a [`crate::solver::Diagnostic`], to also check $\psi$.
+    ///
+    /// # Arguments
+    ///
+    /// - `solver` - The solver that produced the solution.
+    /// - `input` - The measurements.
+    /// - `solution` - The (projected) solution.
+    ///
+    /// # Returns
+    ///
+    /// The global optimality certificate.
+    pub fn certify_solution<R: R2, S: Certifiable<R>>(
+        &self,
+        solver: &S,
+        input: S::Input<'_>,
+        solution: &S::Output,
+    ) -> Certificate {
+        let mut terms = solver.compute_terms(&input);
+        solver.update_terms_cache(&mut terms, &solver.mat_to_vec(solution));
+
+        let (beta, mu) = solver.solve_beta_mu(&terms);
+
+        self.certify_impl(solver, input, solution, &beta, &mu, false)
+    }
+
+    fn certify_impl<R: R2, S: Certifiable<R>>(
+        &self,
+        solver: &S,
+        input: S::Input<'_>,
+        solution: &S::Output,
+        beta: &[f64],
+        mu: &[f64],
+        check_psi: bool,
+    ) -> Certificate {
+        let mut terms = solver.compute_terms(&input);
+        assert!(terms.len() == beta.len(), "Size mismatch of beta and terms");
+
+        let vec = solver.mat_to_vec(solution);
+        solver.update_terms_cache(&mut terms, &vec);
+
+        let psi_norm = solver.compute_psi_norm(beta, mu, &terms);
+
+        // The matrices of the terms drop the constant of the denominator,
+        // which is restored on the homogeneous coordinate.
+        let constant = beta
+            .iter()
+            .zip(mu.iter())
+            .zip(terms.iter())
+            .map(|((b, m), term)| m * b * term.h_constant())
+            .sum::<f64>();
+
+        let mut mat_a = solver.compute_mat_a(beta, mu, &terms);
+        mat_a[[solver.dim() - 1, solver.dim() - 1]] -= constant;
+
+        let objective = vec.t().dot(&mat_a).dot(&vec)[[0, 0]];
+
+        let (constraints, rhs) = solver.constraints();
+        let dual = self.sdp_solver.solve(&mat_a, &constraints, &rhs);
+
+        let scale = 1.0 + mat_a.iter().map(|x| x * x).sum::<f64>().sqrt();
+        let suboptimality_gap = (objective - dual.dual_objective).max(0.0) / scale;
+
+        Certificate {
+            objective,
+            lower_bound: dual.dual_objective,
+            suboptimality_gap,
+            psi_norm,
+            dual_converged: dual.converged,
+            certified: dual.converged
+                && suboptimality_gap < self.gap_tol
+                && (!check_psi || psi_norm < self.psi_tol),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains a numerical checker for the conditions of Proposition
+//! 3 of the paper.
+//!
+//! Let $\alpha = (\beta, \mu)$ and $x_\alpha$ be the solution of the convex
+//! subproblem given $\alpha$. Proposition 3 guarantees the global optimality of
+//! FracGM if
+//!
+//! $$
+//! \psi(\alpha, x_\alpha) = \begin{pmatrix} -f_i(x_\alpha) + \beta_i h_i(x_\alpha) \\\\ -1 + \mu_i h_i(x_\alpha) \end{pmatrix}_i
+//! $$
+//!
+//! is differentiable and Lipschitz continuous in $\alpha$. Appendix A verifies
+//! these conditions analytically on a scalar example. For a concrete problem
+//! instance, the checker samples $\alpha$ around the iterate path of the
+//! solver and estimates both conditions with finite differences. The result is
+//! numerical evidence, not a proof.
+
+use ndarray::{Array1, Array2};
+
+use crate::solver::{
+    Diagnostic, Fractional, FractionalProgrammingMaterials, GemanMcclureSolverDiagnostic, R2,
+};
+
+/// The report of the numerical checker.
+#[derive(Clone)]
+pub struct ConvergenceReport {
+    /// The estimated Lipschitz constant of $\psi$ around each iterate.
+    pub lipschitz_estimates: Vec<f64>,
+    /// The largest estimated Lipschitz constant of $\psi$ along the path.
+    pub lipschitz_constant: f64,
+    /// The largest ratio between the Lipschitz estimates with a small and a
+    /// large sampling radius. It stays close to 1 for a locally Lipschitz
+    /// $\psi$ and grows when the difference quotients blow up.
+    pub lipschitz_growth: f64,
+    /// The largest relative mismatch between forward and backward difference
+    /// quotients along the path. It vanishes for a differentiable $\psi$.
+    pub differentiability_error: f64,
+    /// The norm of $\psi$ at the last iterate.
+    pub psi_norm: f64,
+    /// Whether $\psi$ appears to be Lipschitz continuous.
+    pub lipschitz: bool,
+    /// Whether $\psi$ appears to be differentiable.
+    pub differentiable: bool,
+    /// Whether the solver reaches a root of $\psi$.
+    pub converged: bool,
+    /// Whether all the premises of Proposition 3 appear to hold.
+    pub satisfied: bool,
+}
+
+/// A numerical checker of the conditions of Proposition 3.
+pub struct ConvergenceChecker {
+    /// The number of sampled perturbations around each iterate (default: 8).
+    pub n_samples: usize,
+    /// The sampling radius relative to the norm of $\alpha$ (default: 1e-2).
+    pub radius: f64,
+    /// The finite difference step relative to the norm of $\alpha$ (default:
+    /// 1e-5).
+    pub step: f64,
+    /// The tolerance of the differentiability error (default: 1e-2).
+    pub diff_tol: f64,
+    /// The tolerance of the Lipschitz growth (default: 2.0).
+    pub growth_tol: f64,
+}
+
+impl Default for ConvergenceChecker {
+    fn default() -> Self {
+        Self::new(None, None, None, None, None)
+    }
+}
+
+impl ConvergenceChecker {
+    pub fn new(
+        n_samples: Option<usize>,
+        radius: Option<f64>,
+        step: Option<f64>,
+        diff_tol: Option<f64>,
+        growth_tol: Option<f64>,
+    ) -> Self {
+        Self {
+            n_samples: n_samples.unwrap_or(8),
+            radius: radius.unwrap_or(1e-2),
+            step: step.unwrap_or(1e-5),
+            diff_tol: diff_tol.unwrap_or(1e-2),
+            growth_tol: growth_tol.unwrap_or(2.0),
+        }
+    }
+
+    /// Solve the problem and check the conditions along its iterate path.
+    ///
+    /// # Arguments
+    ///
+    /// - `solver` - The solver to be checked.
+    /// - `input` - The measurements.
+    ///
+    /// # Returns
+    ///
+    /// The report of the numerical checker.
+    pub fn check<R: R2, S: GemanMcclureSolverDiagnostic<R>>(
+        &self,
+        solver: &S,
+        input: S::Input<'_>,
+    ) -> ConvergenceReport {
+        solver.check_input(&input);
+
+        let terms = solver.compute_terms(&input);
+        let init_mat = solver.compute_initial_guess(&input);
+        let diagnostic = solver.solve_terms(terms, &init_mat);
+
+        self.check_diagnostic(solver, input, &diagnostic)
+    }
+
+    /// Check the conditions along the iterate path of a diagnosed solve.
+    ///
+    /// # Arguments
+    ///
+    /// - `solver` - The solver that produced the diagnostic information.
+    /// - `input` - The measurements.
+    /// - `diagnostic` - The diagnostic information of the solver.
+    ///
+    /// # Returns
+    ///
+    /// The report of the numerical checker.
+    pub fn check_diagnostic<R: R2, S: FractionalProgrammingMaterials<R>>(
+        &self,
+        solver: &S,
+        input: S::Input<'_>,
+        diagnostic: &Diagnostic<S::Output>,
+    ) -> ConvergenceReport {
+        let mut terms = solver.compute_terms(&input);
+        let mut rng = Xorshift::new(0x5eed_f7ac_6a11_u64);
+
+        let mut lipschitz_estimates = Vec::new();
+        let mut lipschitz_growth: f64 = 0.0;
+        let mut differentiability_error: f64 = 0.0;
+
+        for component in diagnostic.iterations.iter() {
+            let alpha =
+                Array1::from_iter(component.beta.iter().chain(component.mu.iter()).cloned());
+            let alpha_norm = alpha.dot(&alpha).sqrt();
+            let warm = &component.alpha_vec;
+            let psi = compute_psi(solver, &mut terms, &alpha, warm);
+
+            let mut lipschitz_large: f64 = 0.0;
+            let mut lipschitz_small: f64 = 0.0;
+            for _ in 0..self.n_samples {
+                let direction = rng.unit_vector(alpha.len());
+
+                for (radius, estimate) in [
+                    (self.radius, &mut lipschitz_large),
+                    (0.1 * self.radius, &mut lipschitz_small),
+                ] {
+                    let delta = radius * alpha_norm * &direction;
+                    let psi_delta = compute_psi(solver, &mut terms, &(&alpha + &delta), warm);
+                    *estimate = estimate.max(norm(&(&psi_delta - &psi)) / norm(&delta));
+                }
+
+                let eps = self.step * alpha_norm;
+                let psi_forward =
+                    compute_psi(solver, &mut terms, &(&alpha + &(eps * &direction)), warm);
+                let psi_backward =
+                    compute_psi(solver, &mut terms, &(&alpha - &(eps * &direction)), warm);
+
+                let forward = (&psi_forward - &psi) / eps;
+                let backward = (&psi - &psi_backward) / eps;
+                let scale = (norm(&forward) + norm(&backward)).max(f64::MIN_POSITIVE);
+                differentiability_error =
+                    differentiability_error.max(norm(&(&forward - &backward)) / scale);
+            }
+
+            if lipschitz_large > 0.0 {
+                lipschitz_growth = lipschitz_growth.max(lipschitz_small / lipschitz_large);
+            }
+            lipschitz_estimates.push(lipschitz_large.max(lipschitz_small));
+        }
+
+        let lipschitz_constant = lipschitz_estimates.iter().cloned().fold(0.0, f64::max);
+        let psi_norm = diagnostic
+            .iterations
+            .last()
+            .map_or(f64::INFINITY, |component| component.psi_norm);
+
+        let lipschitz = lipschitz_constant.is_finite() && lipschitz_growth < self.growth_tol;
+        let differentiable = differentiability_error < self.diff_tol;
+        let converged = psi_norm < solver.tol();
+
+        ConvergenceReport {
+            lipschitz_estimates,
+            lipschitz_constant,
+            lipschitz_growth,
+            differentiability_error,
+            psi_norm,
+            lipschitz,
+            differentiable,
+            converged,
+            satisfied: lipschitz && differentiable && converged,
+        }
+    }
+}
+
+/// Evaluates $\psi(\alpha, x_\alpha)$, where $\alpha$ stacks $\beta$ and
+/// $\mu$. The subproblem is solved the same way as in the solver, i.e., with
+/// the sparse backend warm-started at `warm` if the solver uses it.
+fn compute_psi<R: R2, S: FractionalProgrammingMaterials<R>>(
+    solver: &S,
+    terms: &mut Vec<Fractional<R>>,
+    alpha: &Array1<f64>,
+    warm: &Array2<f64>,
+) -> Array1<f64> {
+    let n = terms.len();
+    let beta = alpha.iter().take(n).cloned().collect::<Vec<f64>>();
+    let mu = alpha.iter().skip(n).cloned().collect::<Vec<f64>>();
+
+    let vec = solver.solve_subproblem(&beta, &mu, terms, warm);
+    solver.update_terms_cache(terms, &vec);
+
+    let mut psi = Array1::<f64>::zeros(2 * n);
+    for (i, term) in terms.iter().enumerate() {
+        psi[i] = -term.f() + beta[i] * term.h();
+        psi[n + i] = -1.0 + mu[i] * term.h();
+    }
+
+    psi
+}
+
+fn norm(v: &Array1<f64>) -> f64 {
+    v.dot(v).sqrt()
+}
+
+/// A small xorshift generator, so that the sampled perturbations (and hence
+/// the reports) are reproducible.
+struct Xorshift(u64);
+
+impl Xorshift {
+    fn new(seed: u64) -> Self {
+        Self(seed.max(1))
+    }
+
+    fn next_f64(&mut self) -> f64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+
+        (self.0 >> 11) as f64 / (1u64 << 53) as f64
+    }
+
+    fn unit_vector(&mut self, n: usize) -> Array1<f64> {
+        let v = Array1::from_iter((0..n).map(|_| 2.0 * self.next_f64() - 1.0));
+
+        &v / norm(&v).max(f64::MIN_POSITIVE)
+    }
+}
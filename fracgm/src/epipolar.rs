@@ -0,0 +1,19 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solvers of the two-view epipolar geometry.
+//! Given corresponding image points $x_1$ and $x_2$ of two views, they
+//! estimate the essential matrix (calibrated cameras) or the fundamental
+//! matrix (uncalibrated cameras) $E$ with the algebraic epipolar residual
+//! $x_2^\top E x_1$, which is linear in $\mathrm{vec}(E)$. The points are
+//! Hartley-normalized for numerical conditioning, and the essential matrix can
+//! be decomposed into the relative pose with a cheirality check. It is a
+//! RANSAC-free alternative of the minimal solvers for visual odometry.
+
+pub mod essential;
+pub mod fundamental;
+pub mod utils;
+
+pub use essential::EssentialSolver;
+pub use fundamental::FundamentalSolver;
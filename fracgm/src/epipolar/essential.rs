@@ -0,0 +1,211 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{Array2, Axis};
+
+use crate::epipolar::utils as epi_utils;
+use crate::manifold::{self, Manifold};
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based essential matrix solver with linear (naive) relaxation.
+///
+/// The FracGM iterations run on the Hartley-normalized calibrated image
+/// points in homogeneous coordinates, i.e., the input of [`GemanMcclureSolver`]
+/// has shape `(n, 3)`, and the matrix $\tilde{E}$ of the normalized points is
+/// lifted to $[\mathrm{vec}(\tilde{E}); 1]$ with $\|\tilde{E}\|_F = 1$. Use
+/// [`EssentialSolver::solve_points`] or [`EssentialSolver::solve_pixels`],
+/// which normalize the points and project the solution onto the essential
+/// manifold. The noise bound is in the units of the algebraic residual
+/// $\tilde{x}_2^\top \tilde{E} \tilde{x}_1$ of the normalized points.
+pub struct EssentialSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl EssentialSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Solve the problem with calibrated image points.
+    ///
+    /// # Arguments
+    ///
+    /// - `pts1` - The calibrated image points of the first view with shape `(n, 2)`.
+    /// - `pts2` - The calibrated image points of the second view with shape `(n, 2)`.
+    ///
+    /// # Returns
+    ///
+    /// The 3x3 essential matrix with unit Frobenius norm.
+    pub fn solve_points(&self, pts1: &Array2<f64>, pts2: &Array2<f64>) -> Array2<f64> {
+        let (norm1, transform1) = epi_utils::hartley_normalization(pts1);
+        let (norm2, transform2) = epi_utils::hartley_normalization(pts2);
+
+        let normalized = GemanMcclureSolver::solve(self, (&norm1, &norm2));
+
+        manifold::Essential.project(&epi_utils::denormalize(
+            &normalized,
+            &transform1,
+            &transform2,
+        ))
+    }
+
+    /// Solve the problem with pixel coordinates, which are calibrated with the
+    /// intrinsic matrices.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels1` - The pixel coordinates of the first view with shape `(n, 2)`.
+    /// - `pixels2` - The pixel coordinates of the second view with shape `(n, 2)`.
+    /// - `intrinsics1` - The 3x3 intrinsic matrix of the first camera.
+    /// - `intrinsics2` - The 3x3 intrinsic matrix of the second camera.
+    ///
+    /// # Returns
+    ///
+    /// The 3x3 essential matrix with unit Frobenius norm.
+    pub fn solve_pixels(
+        &self,
+        pixels1: &Array2<f64>,
+        pixels2: &Array2<f64>,
+        intrinsics1: &Array2<f64>,
+        intrinsics2: &Array2<f64>,
+    ) -> Array2<f64> {
+        self.solve_points(
+            &epi_utils::calibrate_pixels(pixels1, intrinsics1),
+            &epi_utils::calibrate_pixels(pixels2, intrinsics2),
+        )
+    }
+
+    /// Classifies the correspondences into inliers and outliers with the given
+    /// essential matrix, i.e., whether the epipolar residuals of the
+    /// normalized points are within the noise bound (scaled by $c$).
+    ///
+    /// # Arguments
+    ///
+    /// - `pts1` - The calibrated image points of the first view with shape `(n, 2)`.
+    /// - `pts2` - The calibrated image points of the second view with shape `(n, 2)`.
+    /// - `essential` - The 3x3 essential matrix.
+    ///
+    /// # Returns
+    ///
+    /// Whether each correspondence is an inlier.
+    pub fn inliers(
+        &self,
+        pts1: &Array2<f64>,
+        pts2: &Array2<f64>,
+        essential: &Array2<f64>,
+    ) -> Vec<bool> {
+        let (norm1, transform1) = epi_utils::hartley_normalization(pts1);
+        let (norm2, transform2) = epi_utils::hartley_normalization(pts2);
+
+        let normalized = epi_utils::normalize(essential, &transform1, &transform2);
+
+        let mut terms = self.compute_terms(&(&norm1, &norm2));
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&self.project(&normalized)));
+
+        utils::compute_inliers(&terms, self.c())
+    }
+
+    /// Decomposes the essential matrix into the relative pose $X_2 = R X_1 +
+    /// t$ with $\|t\| = 1$, where the cheirality check only counts the
+    /// inliers, see [`epi_utils::decompose_essential`].
+    ///
+    /// # Arguments
+    ///
+    /// - `pts1` - The calibrated image points of the first view with shape `(n, 2)`.
+    /// - `pts2` - The calibrated image points of the second view with shape `(n, 2)`.
+    /// - `essential` - The 3x3 essential matrix.
+    ///
+    /// # Returns
+    ///
+    /// The 4x4 relative pose.
+    pub fn relative_pose(
+        &self,
+        pts1: &Array2<f64>,
+        pts2: &Array2<f64>,
+        essential: &Array2<f64>,
+    ) -> Array2<f64> {
+        let inliers = self.inliers(pts1, pts2, essential);
+        let indices = (0..pts1.nrows())
+            .filter(|&k| inliers[k])
+            .collect::<Vec<usize>>();
+
+        epi_utils::decompose_essential(
+            essential,
+            &pts1.select(Axis(0), &indices),
+            &pts2.select(Axis(0), &indices),
+        )
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for EssentialSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        rot_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_vec_to_mat(vec)
+    }
+    /// The normalization does not preserve the essential manifold, so the
+    /// matrix of the normalized points is only projected onto the rank-2
+    /// matrices.
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::Fundamental.project(mat)
+    }
+
+    /// The subproblem is homogeneous in $\tilde{E}$, see
+    /// [`epi_utils::solve_epipolar`].
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        epi_utils::solve_epipolar(mat)
+    }
+
+    fn check_input(&self, (pts1, pts2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pts1, pts2, 3);
+    }
+
+    fn compute_terms(&self, (pts1, pts2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        epi_utils::compute_terms(pts1, pts2, self.noise_bound, self.c())
+    }
+
+    /// The (unweighted) eight-point algorithm, i.e., the least squares
+    /// solution projected onto the rank-2 matrices.
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        self.project(&self.compute_least_squares_initial_guess(&self.compute_terms(input)))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for EssentialSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for EssentialSolver {}
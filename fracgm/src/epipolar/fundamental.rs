@@ -0,0 +1,148 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::Array2;
+
+use crate::epipolar::utils as epi_utils;
+use crate::manifold::{self, Manifold};
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based fundamental matrix solver with linear (naive) relaxation.
+///
+/// As in [`crate::epipolar::EssentialSolver`], the FracGM iterations run on
+/// the Hartley-normalized pixels in homogeneous coordinates with shape `(n,
+/// 3)`, and [`FundamentalSolver::solve_pixels`] maps the solution back to the
+/// original pixels. The noise bound is in the units of the algebraic residual
+/// of the normalized points.
+pub struct FundamentalSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl FundamentalSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Solve the problem with pixel coordinates.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels1` - The pixel coordinates of the first view with shape `(n, 2)`.
+    /// - `pixels2` - The pixel coordinates of the second view with shape `(n, 2)`.
+    ///
+    /// # Returns
+    ///
+    /// The 3x3 fundamental matrix of rank 2 with unit Frobenius norm.
+    pub fn solve_pixels(&self, pixels1: &Array2<f64>, pixels2: &Array2<f64>) -> Array2<f64> {
+        let (norm1, transform1) = epi_utils::hartley_normalization(pixels1);
+        let (norm2, transform2) = epi_utils::hartley_normalization(pixels2);
+
+        let normalized = GemanMcclureSolver::solve(self, (&norm1, &norm2));
+
+        self.project(&epi_utils::denormalize(
+            &normalized,
+            &transform1,
+            &transform2,
+        ))
+    }
+
+    /// Classifies the correspondences into inliers and outliers with the given
+    /// fundamental matrix, i.e., whether the epipolar residuals of the
+    /// normalized pixels are within the noise bound (scaled by $c$).
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels1` - The pixel coordinates of the first view with shape `(n, 2)`.
+    /// - `pixels2` - The pixel coordinates of the second view with shape `(n, 2)`.
+    /// - `fundamental` - The 3x3 fundamental matrix.
+    ///
+    /// # Returns
+    ///
+    /// Whether each correspondence is an inlier.
+    pub fn inliers(
+        &self,
+        pixels1: &Array2<f64>,
+        pixels2: &Array2<f64>,
+        fundamental: &Array2<f64>,
+    ) -> Vec<bool> {
+        let (norm1, transform1) = epi_utils::hartley_normalization(pixels1);
+        let (norm2, transform2) = epi_utils::hartley_normalization(pixels2);
+
+        let normalized = epi_utils::normalize(fundamental, &transform1, &transform2);
+
+        let mut terms = self.compute_terms(&(&norm1, &norm2));
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&self.project(&normalized)));
+
+        utils::compute_inliers(&terms, self.c())
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for FundamentalSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        rot_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::Fundamental.project(mat)
+    }
+
+    /// The subproblem is homogeneous in $\tilde{F}$, see
+    /// [`epi_utils::solve_epipolar`].
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        epi_utils::solve_epipolar(mat)
+    }
+
+    fn check_input(&self, (pts1, pts2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pts1, pts2, 3);
+    }
+
+    fn compute_terms(&self, (pts1, pts2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        epi_utils::compute_terms(pts1, pts2, self.noise_bound, self.c())
+    }
+
+    /// The (unweighted) eight-point algorithm, i.e., the least squares
+    /// solution projected onto the rank-2 matrices.
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        self.project(&self.compute_least_squares_initial_guess(&self.compute_terms(input)))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for FundamentalSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for FundamentalSolver {}
@@ -0,0 +1,240 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::{Determinant, Eigh, Inverse, SVD, UPLO};
+
+use crate::rotation::utils as rot_utils;
+use crate::solver::{Fractional, R2Sym};
+
+/// Converts image points to homogeneous coordinates $[u; v; 1]$.
+pub fn to_homogeneous(points: &Array2<f64>) -> Array2<f64> {
+    assert!(points.ncols() == 2, "Input points must have 2 columns");
+
+    let mut homogeneous = Array2::<f64>::ones((points.nrows(), 3));
+    homogeneous.slice_mut(s![.., 0..2]).assign(points);
+
+    homogeneous
+}
+
+/// Hartley normalization of image points, which moves the centroid to the
+/// origin and scales the mean distance to the origin to $\sqrt{2}$.
+///
+/// # Arguments
+///
+/// - `points` - The image points with shape `(n, 2)`.
+///
+/// # Returns
+///
+/// The normalized points in homogeneous coordinates with shape `(n, 3)` and
+/// the 3x3 normalizing transformation $T$.
+pub fn hartley_normalization(points: &Array2<f64>) -> (Array2<f64>, Array2<f64>) {
+    assert!(points.nrows() > 0, "Input points must not be empty");
+
+    let centroid = points.mean_axis(Axis(0)).unwrap();
+    let centered = points - &centroid;
+    let mean_dist = centered
+        .rows()
+        .into_iter()
+        .map(|row| row.dot(&row).sqrt())
+        .sum::<f64>()
+        / points.nrows() as f64;
+    let scale = match mean_dist > 0.0 {
+        true => 2f64.sqrt() / mean_dist,
+        false => 1.0,
+    };
+
+    let transform = array![
+        [scale, 0.0, -scale * centroid[0]],
+        [0.0, scale, -scale * centroid[1]],
+        [0.0, 0.0, 1.0]
+    ];
+
+    (to_homogeneous(&(scale * centered)), transform)
+}
+
+/// Converts pixel coordinates to calibrated image points, i.e., the first two
+/// entries of $K^{-1} [u; v; 1]$.
+///
+/// # Arguments
+///
+/// - `pixels` - The pixel coordinates with shape `(n, 2)`.
+/// - `intrinsics` - The 3x3 intrinsic matrix $K$.
+///
+/// # Returns
+///
+/// The calibrated image points with shape `(n, 2)`.
+pub fn calibrate_pixels(pixels: &Array2<f64>, intrinsics: &Array2<f64>) -> Array2<f64> {
+    assert!(
+        intrinsics.dim() == (3, 3),
+        "Intrinsic matrix must be a 3x3 matrix"
+    );
+
+    let inv = intrinsics
+        .inv()
+        .expect("Intrinsic matrix must be invertible");
+    let points = to_homogeneous(pixels).dot(&inv.t());
+
+    &points.slice(s![.., 0..2]) / &points.slice(s![.., 2..3])
+}
+
+/// Maps a matrix $\tilde{E}$ of the Hartley-normalized points back to the
+/// original points, i.e., $T_2^\top \tilde{E} T_1$.
+pub fn denormalize(
+    mat: &Array2<f64>,
+    transform1: &Array2<f64>,
+    transform2: &Array2<f64>,
+) -> Array2<f64> {
+    transform2.t().dot(mat).dot(transform1)
+}
+
+/// Maps a matrix $E$ of the original points to the Hartley-normalized points,
+/// i.e., $T_2^{-\top} E T_1^{-1}$.
+pub fn normalize(
+    mat: &Array2<f64>,
+    transform1: &Array2<f64>,
+    transform2: &Array2<f64>,
+) -> Array2<f64> {
+    transform2
+        .inv()
+        .unwrap()
+        .t()
+        .dot(mat)
+        .dot(&transform1.inv().unwrap())
+}
+
+/// Computes the $1 \times 10$ matrix $N$ of the epipolar residual $x_2^\top E
+/// x_1 = (x_1 \otimes x_2)^\top \mathrm{vec}(E)$ in the lifted variable
+/// $[\mathrm{vec}(E); 1]$.
+pub fn epipolar_mat(x1: &ArrayView1<f64>, x2: &ArrayView1<f64>) -> Array2<f64> {
+    let mut mat = Array2::<f64>::zeros((1, rot_utils::DIM));
+    mat.slice_mut(s![0, 0..9]).assign(
+        &kron(
+            &x1.to_owned().into_shape((3, 1)).unwrap(),
+            &x2.to_owned().into_shape((3, 1)).unwrap(),
+        )
+        .column(0),
+    );
+
+    mat
+}
+
+pub fn compute_terms(
+    pts1: &Array2<f64>,
+    pts2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pts1.nrows());
+
+    for (x1, x2) in pts1.rows().into_iter().zip(pts2.rows()) {
+        let mat_n = epipolar_mat(&x1, &x2);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Solves the subproblem $\min_x x^\top A x$ over $x = [\mathrm{vec}(E); 1]$.
+/// The epipolar residuals have no constant term, so the problem is
+/// homogeneous in $E$ and its scale is fixed by $\|E\|_F = 1$ instead, i.e.,
+/// $\mathrm{vec}(E)$ is the eigenvector of the smallest eigenvalue of the top
+/// left block of $A$.
+pub fn solve_epipolar(mat: &Array2<f64>) -> Array2<f64> {
+    let (_, eigvecs) = mat.slice(s![0..9, 0..9]).eigh(UPLO::Lower).unwrap();
+
+    let mut vec = Array2::<f64>::ones((rot_utils::DIM, 1));
+    vec.slice_mut(s![0..9, 0]).assign(&eigvecs.column(0));
+
+    vec
+}
+
+/// Decomposes an essential matrix $E = [t]_\times R$ into the relative pose
+/// $(R, t)$ with $\|t\| = 1$ that maps the points of the first camera frame
+/// into the second one, i.e., $X_2 = R X_1 + t$. Among the four candidates,
+/// the pose with the most triangulated points in front of both cameras is
+/// chosen (cheirality check).
+///
+/// # Arguments
+///
+/// - `essential` - The 3x3 essential matrix.
+/// - `pts1` - The calibrated image points of the first view with shape `(n, 2)`.
+/// - `pts2` - The calibrated image points of the second view with shape `(n, 2)`.
+///
+/// # Returns
+///
+/// The 4x4 relative pose.
+pub fn decompose_essential(
+    essential: &Array2<f64>,
+    pts1: &Array2<f64>,
+    pts2: &Array2<f64>,
+) -> Array2<f64> {
+    let (u, _, vt) = essential.svd(true, true).unwrap();
+    let mut u = u.unwrap();
+    let mut vt = vt.unwrap();
+    if u.det().unwrap() < 0.0 {
+        u.column_mut(2).mapv_inplace(|x| -x);
+    }
+    if vt.det().unwrap() < 0.0 {
+        vt.row_mut(2).mapv_inplace(|x| -x);
+    }
+
+    let w = array![[0.0, -1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+    let rots = [u.dot(&w).dot(&vt), u.dot(&w.t()).dot(&vt)];
+    let trans = u.column(2).to_owned();
+    let pts1 = to_homogeneous(pts1);
+    let pts2 = to_homogeneous(pts2);
+
+    let mut best: Option<(usize, Array2<f64>)> = None;
+    for rot in rots.iter() {
+        for sign in [1.0, -1.0] {
+            let t = sign * &trans;
+            let count = count_in_front(rot, &t, &pts1, &pts2);
+            let better = match &best {
+                Some((n, _)) => count > *n,
+                None => true,
+            };
+            if better {
+                let mut pose = Array2::<f64>::eye(4);
+                pose.slice_mut(s![0..3, 0..3]).assign(rot);
+                pose.slice_mut(s![0..3, 3]).assign(&t);
+                best = Some((count, pose));
+            }
+        }
+    }
+
+    best.unwrap().1
+}
+
+/// Counts the correspondences whose triangulated depths $d_1, d_2$ with $d_2
+/// x_2 = d_1 R x_1 + t$ (in the least squares sense) are both positive.
+fn count_in_front(
+    rot: &Array2<f64>,
+    trans: &Array1<f64>,
+    pts1: &Array2<f64>,
+    pts2: &Array2<f64>,
+) -> usize {
+    pts1.rows()
+        .into_iter()
+        .zip(pts2.rows())
+        .filter(|(x1, x2)| {
+            let mut mat = Array2::<f64>::zeros((3, 2));
+            mat.column_mut(0).assign(&rot.dot(x1));
+            mat.column_mut(1).assign(&x2.mapv(|x| -x));
+
+            match mat.t().dot(&mat).inv() {
+                Ok(inv) => {
+                    let depths = inv.dot(&mat.t().dot(trans)).mapv(|x| -x);
+                    depths[0] > 0.0 && depths[1] > 0.0
+                }
+                Err(_) => false,
+            }
+        })
+        .count()
+}
@@ -0,0 +1,30 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solvers of the hand-eye calibration
+//! problem $A_i X = X B_i$, where $(A_i, B_i)$ are pairs of relative motions
+//! (4x4 homogeneous matrices) and $X$ is the unknown extrinsic transformation.
+
+pub mod decoupled;
+pub mod joint;
+pub mod rotation;
+pub mod translation;
+pub mod utils;
+
+pub use decoupled::Solver as DecoupledSolver;
+pub use joint::JointSolver;
+pub use rotation::RotationSolver;
+pub use translation::TranslationSolver;
+
+use ndarray::Array2;
+
+/// The result of a hand-eye calibration.
+#[derive(Clone)]
+pub struct Calibration {
+    /// The estimated 4x4 transformation $X$.
+    pub transformation: Array2<f64>,
+    /// Whether each motion pair is an inlier, i.e., its residuals are within
+    /// the noise bounds (scaled by $c$).
+    pub inliers: Vec<bool>,
+}
@@ -0,0 +1,77 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::hand_eye::Calibration;
+use crate::hand_eye::RotationSolver;
+use crate::hand_eye::TranslationSolver;
+use crate::solver::{FractionalProgrammingMaterials, GemanMcclureSolver};
+use crate::utils;
+
+/// Rotation-then-translation hand-eye solver.
+pub struct Solver {
+    pub rotation_solver: RotationSolver,
+    pub translation_solver: TranslationSolver,
+}
+
+impl Solver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            rotation_solver: RotationSolver::new(max_iteration, tol, noise_bound, c),
+            translation_solver: TranslationSolver::new(max_iteration, tol, noise_bound, c),
+        }
+    }
+}
+
+impl Solver {
+    /// Solve the rotation from the rotation vectors of the motion pairs, and
+    /// then the translation given the rotation. A motion pair is an inlier if
+    /// it is an inlier of both subproblems.
+    ///
+    /// # Arguments
+    ///
+    /// - `motions_a` - The relative motions $A_i$.
+    /// - `motions_b` - The relative motions $B_i$.
+    ///
+    /// # Returns
+    ///
+    /// The estimated transformation with the inlier flag of each motion pair.
+    pub fn calibrate(&self, motions_a: &[Array2<f64>], motions_b: &[Array2<f64>]) -> Calibration {
+        let rot_input = (motions_a, motions_b);
+        let rot = self.rotation_solver.solve(rot_input);
+
+        let trans_input = (motions_a, motions_b, &rot);
+        let trans = self.translation_solver.solve(trans_input);
+
+        let mut rot_terms = self.rotation_solver.compute_terms(&rot_input);
+        self.rotation_solver
+            .update_terms_cache(&mut rot_terms, &self.rotation_solver.mat_to_vec(&rot));
+
+        let mut trans_terms = self.translation_solver.compute_terms(&trans_input);
+        self.translation_solver.update_terms_cache(
+            &mut trans_terms,
+            &self.translation_solver.mat_to_vec(&trans),
+        );
+
+        let inliers = utils::compute_inliers(&rot_terms, self.rotation_solver.c)
+            .into_iter()
+            .zip(utils::compute_inliers(
+                &trans_terms,
+                self.translation_solver.c,
+            ))
+            .map(|(rot_inlier, trans_inlier)| rot_inlier && trans_inlier)
+            .collect();
+
+        let mut transformation = Array2::<f64>::eye(4);
+        transformation.slice_mut(s![0..3, 0..3]).assign(&rot);
+        transformation.slice_mut(s![0..3, 3..4]).assign(&trans);
+
+        Calibration {
+            transformation,
+            inliers,
+        }
+    }
+}
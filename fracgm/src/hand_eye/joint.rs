@@ -0,0 +1,138 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{Array1, Array2};
+
+use crate::certificate::Certifiable;
+use crate::hand_eye::utils as hand_eye_utils;
+use crate::hand_eye::Calibration;
+use crate::manifold::{self, Manifold};
+use crate::registration::utils as reg_utils;
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based joint hand-eye solver with linear (naive) relaxation.
+///
+/// It estimates $X$ of $A_i X = X B_i$ at once in the lifted variable
+/// $[\mathrm{vec}(R_X); t_X; 1]$, so that a motion pair is rejected as a
+/// whole if either its rotation or its translation residual is large.
+pub struct JointSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) of the rotation residuals (default: 0.1).
+    pub rotation_noise_bound: f64,
+    /// The noise bound (sigma) of the translation residuals (default: 0.1).
+    pub translation_noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl JointSolver {
+    /// Create a solver whose noise bounds of the rotation and the translation
+    /// residuals are `noise_bound`. Use the setters to assign them
+    /// individually.
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        let noise_bound = noise_bound.unwrap_or(0.1);
+
+        Self {
+            max_iteration,
+            tol,
+            rotation_noise_bound: noise_bound,
+            translation_noise_bound: noise_bound,
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    pub fn set_rotation_noise_bound(&mut self, noise_bound: f64) {
+        self.rotation_noise_bound = noise_bound;
+    }
+
+    pub fn set_translation_noise_bound(&mut self, noise_bound: f64) {
+        self.translation_noise_bound = noise_bound;
+    }
+
+    /// Solve the hand-eye calibration problem and classify the motion pairs.
+    ///
+    /// # Arguments
+    ///
+    /// - `motions_a` - The relative motions $A_i$.
+    /// - `motions_b` - The relative motions $B_i$.
+    ///
+    /// # Returns
+    ///
+    /// The estimated transformation with the inlier flag of each motion pair.
+    pub fn calibrate(&self, motions_a: &[Array2<f64>], motions_b: &[Array2<f64>]) -> Calibration {
+        let transformation = GemanMcclureSolver::solve(self, (motions_a, motions_b));
+
+        let mut terms = self.compute_terms(&(motions_a, motions_b));
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&transformation));
+
+        Calibration {
+            transformation,
+            inliers: utils::compute_inliers(&terms, self.c()),
+        }
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for JointSolver {
+    type Input<'a> = (&'a [Array2<f64>], &'a [Array2<f64>]);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SE3.project(mat)
+    }
+
+    fn check_input(&self, (motions_a, motions_b): &Self::Input<'_>) {
+        hand_eye_utils::check_motion_pairs(motions_a, motions_b);
+    }
+
+    fn compute_terms(&self, (motions_a, motions_b): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        hand_eye_utils::compute_joint_terms(
+            motions_a,
+            motions_b,
+            self.rotation_noise_bound,
+            self.translation_noise_bound,
+            self.c(),
+        )
+    }
+
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        reg_utils::compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for JointSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for JointSolver {}
+
+impl Certifiable<R2Sym> for JointSolver {
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        rot_utils::so3_constraints(reg_utils::DIM, 0)
+    }
+}
@@ -0,0 +1,91 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{Array1, Array2};
+
+use crate::certificate::Certifiable;
+use crate::hand_eye::utils as hand_eye_utils;
+use crate::manifold::{self, Manifold};
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+
+/// FracGM-based hand-eye rotation solver with linear (naive) relaxation.
+///
+/// It estimates the rotation $R_X$ of $A_i X = X B_i$ from the rotation
+/// vectors of the motion pairs, which satisfy $R_X \alpha_{B_i} =
+/// \alpha_{A_i}$.
+pub struct RotationSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl RotationSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for RotationSolver {
+    type Input<'a> = (&'a [Array2<f64>], &'a [Array2<f64>]);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        rot_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, rot: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_mat_to_vec(rot)
+    }
+    fn vec_to_mat(&self, rot_vec: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_vec_to_mat(rot_vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SO3.project(mat)
+    }
+
+    fn check_input(&self, (motions_a, motions_b): &Self::Input<'_>) {
+        hand_eye_utils::check_motion_pairs(motions_a, motions_b);
+    }
+
+    fn compute_terms(&self, (motions_a, motions_b): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        hand_eye_utils::compute_rotation_terms(motions_a, motions_b, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, (motions_a, motions_b): &Self::Input<'_>) -> Array2<f64> {
+        hand_eye_utils::compute_rotation_initial_guess(motions_a, motions_b)
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for RotationSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for RotationSolver {}
+
+impl Certifiable<R2Sym> for RotationSolver {
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        rot_utils::so3_constraints(rot_utils::DIM, 0)
+    }
+}
@@ -0,0 +1,100 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::hand_eye::utils as hand_eye_utils;
+use crate::manifold::{Euclidean, Manifold};
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+
+/// The dimension of the lifted variable $[t_X; 1]$.
+const DIM: usize = 4;
+
+/// FracGM-based hand-eye translation solver with linear (naive) relaxation.
+///
+/// Given the rotation $R_X$, it estimates the translation $t_X$ of $A_i X = X
+/// B_i$ from the residuals $(R_{A_i} - I) t_X + t_{A_i} - R_X t_{B_i}$. At
+/// least two motion pairs with non-parallel rotation axes are required.
+pub struct TranslationSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl TranslationSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for TranslationSolver {
+    type Input<'a> = (&'a [Array2<f64>], &'a [Array2<f64>], &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..3, ..]).assign(mat);
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        vec.slice(s![0..3, ..]).to_owned()
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        Euclidean.project(mat)
+    }
+
+    fn check_input(&self, (motions_a, motions_b, rot): &Self::Input<'_>) {
+        hand_eye_utils::check_motion_pairs(motions_a, motions_b);
+        assert!(rot.dim() == (3, 3), "Input rotation must be a 3x3 matrix");
+    }
+
+    fn compute_terms(
+        &self,
+        (motions_a, motions_b, rot): &Self::Input<'_>,
+    ) -> Vec<Fractional<R2Sym>> {
+        hand_eye_utils::compute_translation_terms(
+            motions_a,
+            motions_b,
+            rot,
+            self.noise_bound,
+            self.c(),
+        )
+    }
+
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        self.compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for TranslationSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for TranslationSolver {}
@@ -0,0 +1,212 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::manifold::{self, Manifold};
+use crate::registration::utils as reg_utils;
+use crate::rotation::utils as rot_utils;
+use crate::solver::{Fractional, R2Sym};
+
+/// Checks if two lists of motions are paired 4x4 homogeneous matrices.
+///
+/// # Panics
+///
+/// Panics if the lists have different lengths or a motion is not 4x4.
+pub fn check_motion_pairs(motions_a: &[Array2<f64>], motions_b: &[Array2<f64>]) {
+    assert!(
+        motions_a.len() == motions_b.len(),
+        "Input motions must have the same length"
+    );
+    assert!(
+        motions_a
+            .iter()
+            .chain(motions_b.iter())
+            .all(|motion| motion.dim() == (4, 4)),
+        "Input motions must be 4x4 matrices"
+    );
+}
+
+/// Computes the rotation vector (axis-angle) of a rotation matrix, i.e., the
+/// logarithm map of SO(3).
+pub fn rotation_vector(rot: &ArrayView2<f64>) -> Array1<f64> {
+    let cos = ((rot[[0, 0]] + rot[[1, 1]] + rot[[2, 2]] - 1.0) / 2.0).clamp(-1.0, 1.0);
+    let angle = cos.acos();
+    let skew = array![
+        rot[[2, 1]] - rot[[1, 2]],
+        rot[[0, 2]] - rot[[2, 0]],
+        rot[[1, 0]] - rot[[0, 1]]
+    ];
+
+    if angle < 1e-9 {
+        return 0.5 * skew;
+    }
+
+    if std::f64::consts::PI - angle > 1e-6 {
+        return angle / (2.0 * angle.sin()) * skew;
+    }
+
+    // Near $\pi$, the axis is the dominant column of $(R + I) / 2 = u u^\top$.
+    let k = (0..3)
+        .max_by(|&i, &j| rot[[i, i]].total_cmp(&rot[[j, j]]))
+        .unwrap();
+    let mut axis = rot.column(k).to_owned();
+    axis[k] += 1.0;
+    axis /= axis.dot(&axis).sqrt();
+    if axis.dot(&skew) < 0.0 {
+        axis *= -1.0;
+    }
+
+    angle * axis
+}
+
+/// Computes the rotation vectors of the motions row by row.
+pub fn rotation_vectors(motions: &[Array2<f64>]) -> Array2<f64> {
+    let mut vecs = Array2::<f64>::zeros((motions.len(), 3));
+    for (mut row, motion) in vecs.rows_mut().into_iter().zip(motions.iter()) {
+        row.assign(&rotation_vector(&motion.slice(s![0..3, 0..3])));
+    }
+
+    vecs
+}
+
+/// Computes the Geman-McClure terms of the rotation part. The constraint $R_A
+/// R_X = R_X R_B$ is equivalent to $R_X \alpha_B = \alpha_A$ for the rotation
+/// vectors $\alpha$ of non-trivial motions, which is linear in the lifted
+/// variable $[\mathrm{vec}(R_X); 1]$ with a constant term.
+pub fn compute_rotation_terms(
+    motions_a: &[Array2<f64>],
+    motions_b: &[Array2<f64>],
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    rot_utils::compute_terms(
+        &rotation_vectors(motions_b),
+        &rotation_vectors(motions_a),
+        noise_bound,
+        c,
+    )
+}
+
+/// Computes the least squares rotation aligning the rotation vectors.
+pub fn compute_rotation_initial_guess(
+    motions_a: &[Array2<f64>],
+    motions_b: &[Array2<f64>],
+) -> Array2<f64> {
+    let cov = rotation_vectors(motions_a)
+        .t()
+        .dot(&rotation_vectors(motions_b));
+
+    manifold::SO3.project(&cov)
+}
+
+/// Computes the $3 \times 4$ matrix $N$ such that $N [t_X; 1] = (R_A - I) t_X
+/// + t_A - R_X t_B$ given the rotation $R_X$.
+pub fn translation_mat(
+    motion_a: &Array2<f64>,
+    motion_b: &Array2<f64>,
+    rot: &Array2<f64>,
+) -> Array2<f64> {
+    let mut mat_n = Array2::<f64>::zeros((3, 4));
+    mat_n
+        .slice_mut(s![.., 0..3])
+        .assign(&(&motion_a.slice(s![0..3, 0..3]) - &Array2::<f64>::eye(3)));
+    mat_n
+        .slice_mut(s![.., 3])
+        .assign(&(&motion_a.slice(s![0..3, 3]) - &rot.dot(&motion_b.slice(s![0..3, 3]))));
+
+    mat_n
+}
+
+/// Computes the Geman-McClure terms of the translation part given the rotation
+/// $R_X$.
+pub fn compute_translation_terms(
+    motions_a: &[Array2<f64>],
+    motions_b: &[Array2<f64>],
+    rot: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(motions_a.len());
+
+    for (motion_a, motion_b) in motions_a.iter().zip(motions_b.iter()) {
+        let mat_n = translation_mat(motion_a, motion_b, rot);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Computes the $6 \times 13$ matrix $N$ of the joint residual in the lifted
+/// variable $x = [\mathrm{vec}(R_X); t_X; 1]$, which stacks the rotation
+/// residual $R_X \alpha_B - \alpha_A$ scaled by `rotation_weight` and the
+/// translation residual $(R_A - I) t_X + t_A - R_X t_B$ scaled by
+/// `translation_weight`.
+pub fn joint_mat(
+    motion_a: &Array2<f64>,
+    motion_b: &Array2<f64>,
+    rotation_weight: f64,
+    translation_weight: f64,
+) -> Array2<f64> {
+    let id3 = Array2::<f64>::eye(3);
+    let alpha_a = rotation_vector(&motion_a.slice(s![0..3, 0..3]));
+    let alpha_b = rotation_vector(&motion_b.slice(s![0..3, 0..3]));
+    let trans_b = motion_b.slice(s![0..3, 3]).to_owned();
+
+    let mut mat_n = Array2::<f64>::zeros((6, reg_utils::DIM));
+    mat_n
+        .slice_mut(s![0..3, 0..9])
+        .assign(&kron(&alpha_b.into_shape((1, 3)).unwrap(), &id3));
+    mat_n.slice_mut(s![0..3, 12]).assign(&alpha_a.mapv(|x| -x));
+    mat_n
+        .slice_mut(s![3..6, 0..9])
+        .assign(&kron(&trans_b.into_shape((1, 3)).unwrap(), &id3).mapv(|x| -x));
+    mat_n
+        .slice_mut(s![3..6, 9..12])
+        .assign(&(&motion_a.slice(s![0..3, 0..3]) - &id3));
+    mat_n
+        .slice_mut(s![3..6, 12])
+        .assign(&motion_a.slice(s![0..3, 3]));
+
+    mat_n
+        .slice_mut(s![0..3, ..])
+        .mapv_inplace(|x| rotation_weight * x);
+    mat_n
+        .slice_mut(s![3..6, ..])
+        .mapv_inplace(|x| translation_weight * x);
+
+    mat_n
+}
+
+/// Computes the Geman-McClure terms of the joint problem, where the rotation
+/// and the translation residuals are normalized by their own noise bounds.
+pub fn compute_joint_terms(
+    motions_a: &[Array2<f64>],
+    motions_b: &[Array2<f64>],
+    rotation_noise_bound: f64,
+    translation_noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(motions_a.len());
+
+    for (motion_a, motion_b) in motions_a.iter().zip(motions_b.iter()) {
+        let mat_n = joint_mat(
+            motion_a,
+            motion_b,
+            1.0 / rotation_noise_bound,
+            1.0 / translation_noise_bound,
+        );
+
+        let mat_m = mat_n.t().dot(&mat_n);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
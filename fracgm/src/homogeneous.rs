@@ -0,0 +1,10 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains a generic FracGM solver for user-defined problems
+//! whose residuals are linear in the homogeneous variable $[x; 1]$.
+
+pub mod linear;
+
+pub use linear::LinearSolver;
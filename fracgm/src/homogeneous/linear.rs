@@ -0,0 +1,148 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::manifold::Manifold;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+
+/// FracGM-based solver with linear (naive) relaxation for generic problems.
+///
+/// The $i$-th residual is defined as $r_i = N_i [x; 1]$, where the residual
+/// matrices $N_i$ with `dim + 1` columns are the input of the solver. The solver takes care of
+/// building the Geman-McClure terms, solving the homogeneous linear system of
+/// each iteration, and computing a least squares initial guess.
+///
+/// The unknown $x$ is reshaped to a `shape.0` by `shape.1` matrix in
+/// column-major order (i.e., $x = \mathrm{vec}(X)$), which is the same
+/// convention used by the rotation and registration solvers.
+pub struct LinearSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    /// The shape of the unknown $X$ (default: `(dim, 1)`).
+    pub shape: (usize, usize),
+    /// The feasible set of $X$ (default: no projection).
+    pub manifold: Option<Box<dyn Manifold>>,
+}
+
+impl LinearSolver {
+    pub fn new(
+        dim: usize,
+        max_iteration: usize,
+        tol: f64,
+        noise_bound: Option<f64>,
+        c: Option<f64>,
+    ) -> Self {
+        assert!(dim > 0, "Dimension must be positive");
+
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+            shape: (dim, 1),
+            manifold: None,
+        }
+    }
+
+    /// Sets the shape of the unknown $X$. The product of `rows` and `cols`
+    /// must equal the dimension of the problem.
+    pub fn set_shape(&mut self, rows: usize, cols: usize) {
+        assert!(
+            rows * cols == self.dim() - 1,
+            "Shape must match the dimension of the problem"
+        );
+        self.shape = (rows, cols);
+    }
+
+    /// Sets the feasible set of $X$, onto which the solution is projected.
+    pub fn set_manifold<M: Manifold + 'static>(&mut self, manifold: M) {
+        self.manifold = Some(Box::new(manifold));
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = &'a [Array2<f64>];
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        self.shape.0 * self.shape.1 + 1
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let n = self.dim() - 1;
+
+        let mut vec = Array2::<f64>::ones((n + 1, 1));
+        vec.slice_mut(s![0..n, 0])
+            .assign(&mat.t().iter().cloned().collect::<Array1<f64>>());
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        let (rows, cols) = self.shape;
+
+        let mut mat = vec
+            .slice(s![0..rows * cols, 0])
+            .to_owned()
+            .into_shape((cols, rows))
+            .unwrap();
+        mat.swap_axes(0, 1);
+
+        mat.as_standard_layout().to_owned()
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        match &self.manifold {
+            Some(manifold) => manifold.project(mat),
+            None => mat.clone(),
+        }
+    }
+
+    fn check_input(&self, residuals: &Self::Input<'_>) {
+        assert!(
+            residuals.iter().all(|mat_n| mat_n.dim().1 == self.dim()),
+            "Residual matrices must have dim + 1 columns"
+        );
+    }
+
+    fn compute_terms(&self, residuals: &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(residuals.len());
+
+        for mat_n in residuals.iter() {
+            let mat_m = mat_n.t().dot(mat_n) / (self.noise_bound * self.noise_bound);
+
+            terms.push(Fractional::new(R2Sym::new(mat_m), self.c()));
+        }
+
+        terms
+    }
+
+    /// The least squares solution of all the residuals, projected onto the
+    /// manifold (if any).
+    fn compute_initial_guess(&self, residuals: &Self::Input<'_>) -> Array2<f64> {
+        self.project(&self.compute_least_squares_initial_guess(&self.compute_terms(residuals)))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for LinearSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for LinearSolver {}
@@ -0,0 +1,14 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solver of the planar homography between
+//! two images, e.g., for planar scenes and image stitching. Given
+//! corresponding image points $x_1$ and $x_2$, it estimates the homography $H$
+//! with $x_2 \sim H x_1$ from the direct linear transformation (DLT)
+//! residuals, which are linear in $\mathrm{vec}(H)$.
+
+pub mod linear;
+pub mod utils;
+
+pub use linear::LinearSolver;
@@ -0,0 +1,141 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::Array2;
+
+use crate::epipolar::utils as epi_utils;
+use crate::homography::utils as homo_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based homography solver with linear (naive) relaxation.
+///
+/// The scale of the homography is fixed by $H_{33} = 1$, which is the last
+/// entry of $\mathrm{vec}(H)$, so that the DLT residuals are linear in the
+/// homogeneous variable and each subproblem is solved by
+/// [`utils::solve_homogeneous`]. The FracGM iterations run on the
+/// Hartley-normalized points in homogeneous coordinates, i.e., the input of
+/// [`GemanMcclureSolver`] has shape `(n, 3)`. Use
+/// [`LinearSolver::solve_points`] for image points, which normalizes them and
+/// maps the solution back. The noise bound is in the units of the normalized
+/// points, i.e., about the reprojection error times $\sqrt{2}$ over the mean
+/// distance of the points to their centroid.
+pub struct LinearSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl LinearSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Solve the problem with image points.
+    ///
+    /// # Arguments
+    ///
+    /// - `pts1` - The image points of the first view with shape `(n, 2)`.
+    /// - `pts2` - The image points of the second view with shape `(n, 2)`.
+    ///
+    /// # Returns
+    ///
+    /// The 3x3 homography $H$ with $x_2 \sim H x_1$ and $H_{33} = 1$.
+    pub fn solve_points(&self, pts1: &Array2<f64>, pts2: &Array2<f64>) -> Array2<f64> {
+        let (norm1, transform1) = epi_utils::hartley_normalization(pts1);
+        let (norm2, transform2) = epi_utils::hartley_normalization(pts2);
+
+        let normalized = GemanMcclureSolver::solve(self, (&norm1, &norm2));
+        let mat = homo_utils::denormalize(&normalized, &transform1, &transform2);
+
+        self.vec_to_mat(&self.mat_to_vec(&mat))
+    }
+
+    /// Classifies the correspondences into inliers and outliers with the given
+    /// homography, i.e., whether the DLT residuals of the normalized points
+    /// are within the noise bound (scaled by $c$).
+    ///
+    /// # Arguments
+    ///
+    /// - `pts1` - The image points of the first view with shape `(n, 2)`.
+    /// - `pts2` - The image points of the second view with shape `(n, 2)`.
+    /// - `homography` - The 3x3 homography.
+    ///
+    /// # Returns
+    ///
+    /// Whether each correspondence is an inlier.
+    pub fn inliers(
+        &self,
+        pts1: &Array2<f64>,
+        pts2: &Array2<f64>,
+        homography: &Array2<f64>,
+    ) -> Vec<bool> {
+        let (norm1, transform1) = epi_utils::hartley_normalization(pts1);
+        let (norm2, transform2) = epi_utils::hartley_normalization(pts2);
+
+        let normalized = homo_utils::normalize(homography, &transform1, &transform2);
+
+        let mut terms = self.compute_terms(&(&norm1, &norm2));
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&normalized));
+
+        utils::compute_inliers(&terms, self.c())
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        homo_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        homo_utils::homography_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        homo_utils::homography_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        mat.clone()
+    }
+
+    fn check_input(&self, (pts1, pts2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pts1, pts2, 3);
+    }
+
+    fn compute_terms(&self, (pts1, pts2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        homo_utils::compute_terms(pts1, pts2, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        self.compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for LinearSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for LinearSolver {}
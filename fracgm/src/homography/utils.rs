@@ -0,0 +1,93 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::Inverse;
+
+use crate::solver::{Fractional, R2Sym};
+
+/// The dimension of the lifted variable $\mathrm{vec}(H)$, whose last entry
+/// $H_{33}$ is fixed to 1.
+pub const DIM: usize = 9;
+
+/// Converts a homography to the lifted variable $\mathrm{vec}(H) / H_{33}$,
+/// whose last entry is the homogeneous coordinate.
+pub fn homography_mat_to_vec(mat: &Array2<f64>) -> Array2<f64> {
+    assert!(mat.dim() == (3, 3), "Homography must be a 3x3 matrix");
+    assert!(mat[[2, 2]] != 0.0, "Homography must have nonzero H_33");
+
+    mat.t()
+        .iter()
+        .map(|x| x / mat[[2, 2]])
+        .collect::<Array1<f64>>()
+        .into_shape((DIM, 1))
+        .unwrap()
+}
+
+pub fn homography_vec_to_mat(vec: &Array2<f64>) -> Array2<f64> {
+    let mut mat = vec.clone().into_shape((3, 3)).unwrap();
+    mat.swap_axes(0, 1);
+
+    mat.as_standard_layout().to_owned()
+}
+
+/// Maps a homography $\tilde{H}$ of the Hartley-normalized points back to the
+/// original points, i.e., $T_2^{-1} \tilde{H} T_1$.
+pub fn denormalize(
+    mat: &Array2<f64>,
+    transform1: &Array2<f64>,
+    transform2: &Array2<f64>,
+) -> Array2<f64> {
+    transform2.inv().unwrap().dot(mat).dot(transform1)
+}
+
+/// Maps a homography $H$ of the original points to the Hartley-normalized
+/// points, i.e., $T_2 H T_1^{-1}$.
+pub fn normalize(
+    mat: &Array2<f64>,
+    transform1: &Array2<f64>,
+    transform2: &Array2<f64>,
+) -> Array2<f64> {
+    transform2.dot(mat).dot(&transform1.inv().unwrap())
+}
+
+/// Computes the $2 \times 9$ matrix $N$ of the DLT residual
+/// $$
+/// \begin{bmatrix}
+///   u_2 h_3^\top x_1 - h_1^\top x_1 \\\\
+///   v_2 h_3^\top x_1 - h_2^\top x_1
+/// \end{bmatrix}
+/// $$
+/// in the lifted variable $\mathrm{vec}(H)$, where $h_k^\top$ is the $k$-th
+/// row of $H$, $x_1$ is in homogeneous coordinates and $x_2 \sim [u_2; v_2;
+/// 1]$.
+pub fn dlt_mat(x1: &ArrayView1<f64>, x2: &ArrayView1<f64>) -> Array2<f64> {
+    let u2 = x2[0] / x2[2];
+    let v2 = x2[1] / x2[2];
+
+    let rows = array![[-1.0, 0.0, u2], [0.0, -1.0, v2]];
+
+    kron(&x1.to_owned().into_shape((1, 3)).unwrap(), &rows)
+}
+
+pub fn compute_terms(
+    pts1: &Array2<f64>,
+    pts2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pts1.nrows());
+
+    for (x1, x2) in pts1.rows().into_iter().zip(pts2.rows()) {
+        let mat_n = dlt_mat(&x1, &x2);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
@@ -2,10 +2,26 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+pub mod averaging;
+pub mod certificate;
+pub mod convergence;
+pub mod epipolar;
+pub mod hand_eye;
+pub mod homogeneous;
+pub mod homography;
+pub mod manifold;
+pub mod pnp;
+pub mod pose_graph;
+pub mod primitive;
 pub mod registration;
+pub mod regression;
 pub mod rotation;
+pub mod scale;
+pub mod sdp;
 pub mod solver;
+pub mod sparse;
 pub mod translation;
+pub mod triangulation;
 pub mod utils;
 
 #[cfg(feature = "mcis")]
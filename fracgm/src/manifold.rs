@@ -0,0 +1,236 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the feasible sets (manifolds) that a FracGM solution
+//! can be projected onto.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::Determinant;
+use ndarray_linalg::SVD;
+
+/// A trait for a feasible set of a FracGM problem.
+///
+/// The FracGM solver works on a relaxed (lifted) variable, so the solution of
+/// the relaxed problem is projected back to the feasible set at the end.
+pub trait Manifold: Send + Sync {
+    /// Project a matrix onto the manifold.
+    ///
+    /// # Arguments
+    ///
+    /// - `mat` - The input matrix.
+    ///
+    /// # Returns
+    ///
+    /// The projected matrix.
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64>;
+}
+
+/// The special orthogonal group SO(2).
+pub const SO2: SpecialOrthogonal = SpecialOrthogonal { dim: 2 };
+/// The special orthogonal group SO(3).
+pub const SO3: SpecialOrthogonal = SpecialOrthogonal { dim: 3 };
+/// The special Euclidean group SE(2).
+pub const SE2: SpecialEuclidean = SpecialEuclidean { dim: 2 };
+/// The special Euclidean group SE(3).
+pub const SE3: SpecialEuclidean = SpecialEuclidean { dim: 3 };
+/// The similarity group Sim(3).
+pub const SIM3: Similarity = Similarity { dim: 3 };
+
+/// The Euclidean space, i.e., no projection is performed.
+#[derive(Clone, Copy)]
+pub struct Euclidean;
+
+impl Manifold for Euclidean {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        mat.clone()
+    }
+}
+
+/// The unit sphere, i.e., matrices with unit Frobenius norm.
+#[derive(Clone, Copy)]
+pub struct UnitSphere;
+
+impl Manifold for UnitSphere {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let norm = mat.iter().map(|x| x * x).sum::<f64>().sqrt();
+        assert!(norm > 0.0, "Cannot project the zero matrix onto the sphere");
+
+        mat / norm
+    }
+}
+
+/// The orthogonal group O(d).
+#[derive(Clone, Copy)]
+pub struct Orthogonal {
+    /// The dimension $d$.
+    pub dim: usize,
+}
+
+impl Orthogonal {
+    pub fn new(dim: usize) -> Self {
+        Self { dim }
+    }
+}
+
+impl Manifold for Orthogonal {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        check_square(mat, self.dim);
+
+        let (u, _, vt) = mat.svd(true, true).unwrap();
+
+        u.unwrap().dot(&vt.unwrap())
+    }
+}
+
+/// The special orthogonal group SO(d).
+#[derive(Clone, Copy)]
+pub struct SpecialOrthogonal {
+    /// The dimension $d$.
+    pub dim: usize,
+}
+
+impl SpecialOrthogonal {
+    pub fn new(dim: usize) -> Self {
+        Self { dim }
+    }
+}
+
+impl Manifold for SpecialOrthogonal {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        check_square(mat, self.dim);
+
+        project_scaled_rotation(mat).1
+    }
+}
+
+/// The special Euclidean group SE(d), represented by $(d+1) \times (d+1)$
+/// homogeneous transformation matrices.
+#[derive(Clone, Copy)]
+pub struct SpecialEuclidean {
+    /// The dimension $d$.
+    pub dim: usize,
+}
+
+impl SpecialEuclidean {
+    pub fn new(dim: usize) -> Self {
+        Self { dim }
+    }
+}
+
+impl Manifold for SpecialEuclidean {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let d = self.dim;
+        check_square(mat, d + 1);
+
+        let rot = project_scaled_rotation(&mat.slice(s![0..d, 0..d]).to_owned()).1;
+
+        homogeneous(&rot, &mat.slice(s![0..d, d]).to_owned())
+    }
+}
+
+/// The similarity group Sim(d), represented by $(d+1) \times (d+1)$
+/// homogeneous transformation matrices whose top-left block is $sR$ with $s >
+/// 0$ and $R \in SO(d)$.
+#[derive(Clone, Copy)]
+pub struct Similarity {
+    /// The dimension $d$.
+    pub dim: usize,
+}
+
+impl Similarity {
+    pub fn new(dim: usize) -> Self {
+        Self { dim }
+    }
+}
+
+impl Manifold for Similarity {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let d = self.dim;
+        check_square(mat, d + 1);
+
+        let (scale, rot) = project_scaled_rotation(&mat.slice(s![0..d, 0..d]).to_owned());
+
+        homogeneous(&(scale.max(0.0) * rot), &mat.slice(s![0..d, d]).to_owned())
+    }
+}
+
+/// The essential matrices $[t]_\times R$ with unit Frobenius norm, i.e.,
+/// 3x3 matrices whose singular values are $(1, 1, 0) / \sqrt{2}$.
+#[derive(Clone, Copy)]
+pub struct Essential;
+
+impl Manifold for Essential {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        check_square(mat, 3);
+
+        let (u, _, vt) = mat.svd(true, true).unwrap();
+        let sigma = array![1.0, 1.0, 0.0] / 2f64.sqrt();
+
+        u.unwrap().dot(&Array2::from_diag(&sigma)).dot(&vt.unwrap())
+    }
+}
+
+/// The fundamental matrices with unit Frobenius norm, i.e., 3x3 matrices of
+/// rank 2. A matrix of rank 1 has no closest rank-2 matrix, so it is only
+/// normalized, and the zero matrix is returned as is.
+#[derive(Clone, Copy)]
+pub struct Fundamental;
+
+impl Manifold for Fundamental {
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        check_square(mat, 3);
+
+        let (u, mut sigma, vt) = mat.svd(true, true).unwrap();
+        sigma[2] = 0.0;
+        let norm = sigma.dot(&sigma).sqrt();
+        if norm == 0.0 {
+            return Array2::zeros((3, 3));
+        }
+
+        u.unwrap()
+            .dot(&Array2::from_diag(&(sigma / norm)))
+            .dot(&vt.unwrap())
+    }
+}
+
+fn check_square(mat: &Array2<f64>, dim: usize) {
+    assert!(mat.dim().0 == mat.dim().1, "Matrix must be square");
+    assert!(mat.dim().0 == dim, "Matrix must be {}x{}", dim, dim);
+}
+
+/// Finds the closest scaled rotation $sR$ to the given matrix in the sense of
+/// Frobenius norm.
+///
+/// # Returns
+///
+/// The scale $s$ and the rotation matrix $R$.
+fn project_scaled_rotation(mat: &Array2<f64>) -> (f64, Array2<f64>) {
+    let d = mat.dim().0;
+
+    let (u, sigma, vt) = mat.svd(true, true).unwrap();
+
+    let u = u.unwrap();
+    let vt = vt.unwrap();
+
+    let mut diag = Array1::<f64>::ones(d);
+    if u.dot(&vt).det().unwrap() < 0.0 {
+        diag[d - 1] = -1.0;
+    }
+
+    let rot = u.dot(&Array2::from_diag(&diag)).dot(&vt);
+    let scale = sigma.dot(&diag) / d as f64;
+
+    (scale, rot)
+}
+
+fn homogeneous(block: &Array2<f64>, trans: &Array1<f64>) -> Array2<f64> {
+    let d = block.dim().0;
+
+    let mut mat = Array2::<f64>::eye(d + 1);
+    mat.slice_mut(s![0..d, 0..d]).assign(block);
+    mat.slice_mut(s![0..d, d]).assign(trans);
+
+    mat
+}
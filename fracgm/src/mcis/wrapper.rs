@@ -4,7 +4,7 @@
 
 extern crate libc;
 
-use ndarray::Array2;
+use ndarray::{s, Array2};
 use num_cpus;
 
 use crate::mcis::array_helper;
@@ -51,6 +51,9 @@ extern "C" {
 /// The max clique solver is run with the number of threads equal to the number
 /// of logical CPU cores available.
 ///
+/// Planar point clouds (with two columns) are supported by embedding them into
+/// the plane $z = 0$, which preserves the pairwise distances.
+///
 /// # Returns
 ///
 /// Indices of inlier correspondences.
@@ -62,8 +65,8 @@ pub fn max_clique_inlier_selection(
 ) -> Vec<usize> {
     let pmc_n_threads = num_cpus::get() as i32;
 
-    let src = array_helper::to_f64_buf(pc1);
-    let dst = array_helper::to_f64_buf(pc2);
+    let src = array_helper::to_f64_buf(&to_spatial_point_cloud(pc1));
+    let dst = array_helper::to_f64_buf(&to_spatial_point_cloud(pc2));
 
     unsafe {
         let buf = inlier_selection(
@@ -85,3 +88,16 @@ pub fn max_clique_inlier_selection(
         result.into_iter().map(|x| x as usize).collect()
     }
 }
+
+fn to_spatial_point_cloud(pc: &Array2<f64>) -> Array2<f64> {
+    let n_dims = pc.dim().1;
+    assert!(
+        n_dims == 2 || n_dims == 3,
+        "Input point clouds must have 2 or 3 columns"
+    );
+
+    let mut spatial_pc = Array2::<f64>::zeros((pc.dim().0, 3));
+    spatial_pc.slice_mut(s![.., 0..n_dims]).assign(pc);
+
+    spatial_pc
+}
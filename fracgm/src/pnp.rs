@@ -0,0 +1,13 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solver of the absolute camera pose
+//! (perspective-n-point) problem. Given the bearing vectors $v_i$ of a camera
+//! and the corresponding 3D points $p_i$, it estimates the pose $(R, t)$ that
+//! maps the points into the camera frame, i.e., $R p_i + t \parallel v_i$.
+
+pub mod linear;
+pub mod utils;
+
+pub use linear::LinearSolver;
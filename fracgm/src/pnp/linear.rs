@@ -0,0 +1,147 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::Array2;
+
+use crate::manifold::{self, Manifold};
+use crate::pnp::utils as pnp_utils;
+use crate::registration::utils as reg_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based absolute camera pose solver with linear (naive) relaxation.
+///
+/// The object-space residual $(I - v v^\top) (R p + t)$ of a unit bearing
+/// vector $v$ and a 3D point $p$ is linear in the lifted variable
+/// $[\mathrm{vec}(R); t; 1]$. It is the distance from the transformed point to
+/// the line of sight, so the noise bound is in the units of the 3D points.
+///
+/// Each subproblem is solved approximately over SE(3), see
+/// [`pnp_utils::solve_pose`]. Since the iterates are projected, they are not
+/// the solutions of the (relaxed) subproblems, and the convergence guarantee
+/// of FracGM does not apply, as in [`crate::averaging::RotationSolver`]. For
+/// the same reason, and since the relaxation is homogeneous, the solver does
+/// not implement [`crate::certificate::Certifiable`].
+pub struct LinearSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl LinearSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Solve the problem with pixel coordinates, which are converted to
+    /// bearing vectors with the intrinsic matrix.
+    ///
+    /// # Arguments
+    ///
+    /// - `pixels` - The pixel coordinates with shape `(n, 2)`.
+    /// - `intrinsics` - The 3x3 intrinsic matrix $K$.
+    /// - `points` - The 3D points with shape `(n, 3)`.
+    ///
+    /// # Returns
+    ///
+    /// The 4x4 pose that maps the points into the camera frame.
+    pub fn solve_pixels(
+        &self,
+        pixels: &Array2<f64>,
+        intrinsics: &Array2<f64>,
+        points: &Array2<f64>,
+    ) -> Array2<f64> {
+        let bearings = pnp_utils::pixels_to_bearings(pixels, intrinsics);
+
+        GemanMcclureSolver::solve(self, (&bearings, points))
+    }
+
+    /// Classifies the correspondences into inliers and outliers at the given
+    /// pose, i.e., whether the object-space residuals are within the noise
+    /// bound (scaled by $c$).
+    ///
+    /// # Arguments
+    ///
+    /// - `bearings` - The bearing vectors with shape `(n, 3)`.
+    /// - `points` - The 3D points with shape `(n, 3)`.
+    /// - `pose` - The 4x4 pose.
+    ///
+    /// # Returns
+    ///
+    /// Whether each correspondence is an inlier.
+    pub fn inliers(
+        &self,
+        bearings: &Array2<f64>,
+        points: &Array2<f64>,
+        pose: &Array2<f64>,
+    ) -> Vec<bool> {
+        let mut terms = self.compute_terms(&(bearings, points));
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(pose));
+
+        utils::compute_inliers(&terms, self.c())
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SE3.project(mat)
+    }
+
+    /// The subproblem is homogeneous in the pose, see
+    /// [`pnp_utils::solve_pose`].
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        pnp_utils::solve_pose(mat)
+    }
+
+    fn check_input(&self, (bearings, points): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(bearings, points, 3);
+    }
+
+    fn compute_terms(&self, (bearings, points): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        pnp_utils::compute_terms(bearings, points, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        self.compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for LinearSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for LinearSolver {}
@@ -0,0 +1,144 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::{Determinant, Eigh, Inverse, UPLO};
+
+use crate::manifold::{self, Manifold};
+use crate::registration::utils as reg_utils;
+use crate::rotation::utils as rot_utils;
+use crate::solver::{Fractional, R2Sym};
+
+/// Converts pixel coordinates to unit bearing vectors $K^{-1} [u; v; 1]$ with
+/// the intrinsic matrix $K$.
+///
+/// # Arguments
+///
+/// - `pixels` - The pixel coordinates with shape `(n, 2)`.
+/// - `intrinsics` - The 3x3 intrinsic matrix $K$.
+///
+/// # Returns
+///
+/// The bearing vectors with shape `(n, 3)`.
+pub fn pixels_to_bearings(pixels: &Array2<f64>, intrinsics: &Array2<f64>) -> Array2<f64> {
+    assert!(pixels.ncols() == 2, "Input pixels must have 2 columns");
+    assert!(
+        intrinsics.dim() == (3, 3),
+        "Intrinsic matrix must be a 3x3 matrix"
+    );
+
+    let inv = intrinsics
+        .inv()
+        .expect("Intrinsic matrix must be invertible");
+
+    let mut bearings = Array2::<f64>::ones((pixels.nrows(), 3));
+    bearings.slice_mut(s![.., 0..2]).assign(pixels);
+    let mut bearings = bearings.dot(&inv.t());
+    for mut bearing in bearings.rows_mut() {
+        let norm = bearing.dot(&bearing).sqrt();
+        bearing /= norm;
+    }
+
+    bearings
+}
+
+/// Computes the $3 \times 13$ matrix $N$ of the object-space residual $(I - v
+/// v^\top) (R p + t)$ in the lifted variable $x = [\mathrm{vec}(R); t; 1]$,
+/// i.e., the offset of the transformed point from the line of sight of the
+/// unit bearing vector $v$.
+pub fn object_space_mat(bearing: &ArrayView1<f64>, point: &ArrayView1<f64>) -> Array2<f64> {
+    let v = bearing.to_owned() / bearing.dot(bearing).sqrt();
+    let v_col = v.view().into_shape((3, 1)).unwrap();
+    let proj = Array2::<f64>::eye(3) - v_col.dot(&v_col.t());
+
+    let mut mat = Array2::<f64>::zeros((3, reg_utils::DIM));
+    mat.slice_mut(s![.., 0..9]).assign(&kron(
+        &point.to_owned().into_shape((1, 3)).unwrap(),
+        &Array2::eye(3),
+    ));
+    mat.slice_mut(s![.., 9..12]).assign(&Array2::eye(3));
+
+    proj.dot(&mat)
+}
+
+pub fn compute_terms(
+    bearings: &Array2<f64>,
+    points: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(bearings.nrows());
+
+    for (bearing, point) in bearings.rows().into_iter().zip(points.rows()) {
+        let mat_n = object_space_mat(&bearing, &point);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Solves the subproblem $\min_x x^\top A x$ over $x = [\mathrm{vec}(R); t;
+/// 1]$. The object-space residuals have no constant term, so the relaxed
+/// problem is homogeneous in $(R, t)$ and its scale is fixed by
+/// $\|R\|_F^2 = 3$ instead. The translation is eliminated with the Schur
+/// complement, the rotation is the eigenvector of its smallest eigenvalue
+/// (with the sign of positive determinant) projected onto SO(3), and the
+/// translation is recovered given the rotation. If the translation block is
+/// singular, e.g., the weights of all but a few nearly parallel bearing
+/// vectors vanish, its pseudo-inverse is used instead.
+///
+/// Without the projection, the relaxed rotation of a weighted subproblem
+/// tends to collapse to a rank-deficient matrix that maps all the points close
+/// to one line of sight, so the projected pose is an approximate solution of
+/// the subproblem over SE(3) rather than of its relaxation.
+pub fn solve_pose(mat: &Array2<f64>) -> Array2<f64> {
+    let a_rr = mat.slice(s![0..9, 0..9]);
+    let a_rt = mat.slice(s![0..9, 9..12]);
+    let a_tt_inv = pseudo_inverse(&mat.slice(s![9..12, 9..12]).to_owned());
+
+    let schur = &a_rr - &a_rt.dot(&a_tt_inv).dot(&a_rt.t());
+    let (_, eigvecs) = schur.eigh(UPLO::Lower).unwrap();
+
+    let mut rot_vec = Array2::<f64>::ones((rot_utils::DIM, 1));
+    rot_vec
+        .slice_mut(s![0..9, 0])
+        .assign(&(3f64.sqrt() * &eigvecs.column(0)));
+    let mut rot = rot_utils::rot_vec_to_mat(&rot_vec);
+    if rot.det().unwrap() < 0.0 {
+        rot.mapv_inplace(|x| -x);
+    }
+    let rot = manifold::SO3.project(&rot);
+
+    let r = rot_utils::rot_mat_to_vec(&rot).slice_move(s![0..9, 0]);
+    let t: Array1<f64> = a_tt_inv.dot(&a_rt.t().dot(&r));
+
+    let mut vec = Array2::<f64>::ones((reg_utils::DIM, 1));
+    vec.slice_mut(s![0..9, 0]).assign(&r);
+    vec.slice_mut(s![9..12, 0]).assign(&-t);
+
+    vec
+}
+
+/// The relative tolerance of the eigenvalues below which they are regarded as
+/// zero in [`pseudo_inverse`].
+const PINV_TOL: f64 = 1e-12;
+
+/// Computes the pseudo-inverse of a symmetric matrix from its
+/// eigendecomposition.
+fn pseudo_inverse(mat: &Array2<f64>) -> Array2<f64> {
+    let (eigvals, eigvecs) = mat.eigh(UPLO::Lower).unwrap();
+    let max = eigvals.iter().fold(0.0f64, |max, x| max.max(x.abs()));
+
+    let inv = eigvals.mapv(|x| match x.abs() > PINV_TOL * max {
+        true => 1.0 / x,
+        false => 0.0,
+    });
+
+    (&eigvecs * &inv).dot(&eigvecs.t())
+}
@@ -0,0 +1,17 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solver of robust SE(3) pose-graph
+//! optimization, where some loop closures may be false. Its terms are built
+//! on [`crate::solver::R2Sparse`] and the subproblems are solved with the
+//! sparse backend in [`crate::sparse`]. Pose graphs can be read from and
+//! written to g2o and TORO files with [`io`].
+
+pub mod io;
+pub mod linear;
+pub mod utils;
+
+pub use io::PoseGraph;
+pub use linear::LinearSolver;
+pub use utils::Edge;
@@ -0,0 +1,313 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! Readers and writers of pose graphs in the g2o (`VERTEX_SE3:QUAT` and
+//! `EDGE_SE3:QUAT`) and TORO (`VERTEX3` and `EDGE3`) formats.
+//!
+//! The information matrix of an edge is converted to the noise bounds of the
+//! residuals of [`crate::pose_graph::LinearSolver`], i.e., the expected norms
+//! of the chordal rotation residual and the translation residual. An edge
+//! between consecutive vertex ids $k$ and $k + 1$ is an odometry edge. The
+//! writers store isotropic information matrices with the same noise bounds.
+
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::Inverse;
+
+use crate::pose_graph::utils::Edge;
+use crate::rotation::utils as rot_utils;
+
+/// The scale between the trace of the covariance of the rotation part and the
+/// squared norm of the chordal residual, where the rotation part of g2o is
+/// the vector part of the quaternion (half of the rotation vector).
+const G2O_ROTATION_SCALE: f64 = 8.0;
+
+/// The same scale for TORO, whose rotation part is the Euler angles (the
+/// rotation vector for small errors).
+const TORO_ROTATION_SCALE: f64 = 2.0;
+
+/// A pose graph with the ids of the vertices in the file.
+#[derive(Clone)]
+pub struct PoseGraph {
+    /// The ids of the vertices in ascending order.
+    pub ids: Vec<usize>,
+    /// The poses of the vertices as 4x4 homogeneous matrices.
+    pub poses: Vec<Array2<f64>>,
+    /// The edges, whose nodes are the indices of the vertices in `ids`.
+    pub edges: Vec<Edge>,
+}
+
+impl PoseGraph {
+    /// Get the number of nodes.
+    pub fn n_nodes(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Read a pose graph from a g2o file.
+    pub fn read_g2o<P: AsRef<Path>>(path: P) -> io::Result<PoseGraph> {
+        read(path, "VERTEX_SE3:QUAT", "EDGE_SE3:QUAT", 7, |values| {
+            let quat = array![values[6], values[3], values[4], values[5]];
+            pose(&values[0..3], rot_utils::quat_to_rot_mat(&quat))
+        })
+        .and_then(|raw| raw.into_graph(G2O_ROTATION_SCALE))
+    }
+
+    /// Write the pose graph to a g2o file.
+    pub fn write_g2o<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(
+            path,
+            "VERTEX_SE3:QUAT",
+            "EDGE_SE3:QUAT",
+            G2O_ROTATION_SCALE,
+            |pose| {
+                let quat = rot_utils::rot_mat_to_quat(&pose.slice(s![0..3, 0..3]).to_owned());
+                vec![quat[1], quat[2], quat[3], quat[0]]
+            },
+        )
+    }
+
+    /// Read a pose graph from a TORO file.
+    pub fn read_toro<P: AsRef<Path>>(path: P) -> io::Result<PoseGraph> {
+        read(path, "VERTEX3", "EDGE3", 6, |values| {
+            pose(
+                &values[0..3],
+                euler_to_rot_mat(values[3], values[4], values[5]),
+            )
+        })
+        .and_then(|raw| raw.into_graph(TORO_ROTATION_SCALE))
+    }
+
+    /// Write the pose graph to a TORO file.
+    pub fn write_toro<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        self.write(path, "VERTEX3", "EDGE3", TORO_ROTATION_SCALE, |pose| {
+            let (roll, pitch, yaw) = rot_mat_to_euler(&pose.slice(s![0..3, 0..3]));
+            vec![roll, pitch, yaw]
+        })
+    }
+
+    fn write<P: AsRef<Path>, F: Fn(&Array2<f64>) -> Vec<f64>>(
+        &self,
+        path: P,
+        vertex_tag: &str,
+        edge_tag: &str,
+        rotation_scale: f64,
+        rotation_values: F,
+    ) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+
+        let format = |pose: &Array2<f64>| {
+            pose.slice(s![0..3, 3])
+                .iter()
+                .cloned()
+                .chain(rotation_values(pose))
+                .map(|x| x.to_string())
+                .collect::<Vec<String>>()
+                .join(" ")
+        };
+
+        for (id, pose) in self.ids.iter().zip(self.poses.iter()) {
+            writeln!(writer, "{} {} {}", vertex_tag, id, format(pose))?;
+        }
+
+        for edge in self.edges.iter() {
+            // Isotropic information matrix, whose covariance has the traces
+            // $\sigma_t^2$ and $\sigma_R^2 / s$ of the two parts.
+            let mut info = Array2::<f64>::eye(6);
+            if let Some(noise_bound) = edge.translation_noise_bound {
+                info.slice_mut(s![0..3, 0..3])
+                    .mapv_inplace(|x| x * 3.0 / (noise_bound * noise_bound));
+            }
+            if let Some(noise_bound) = edge.rotation_noise_bound {
+                info.slice_mut(s![3..6, 3..6])
+                    .mapv_inplace(|x| x * 3.0 * rotation_scale / (noise_bound * noise_bound));
+            }
+            let info = (0..6)
+                .flat_map(|r| (r..6).map(move |c| (r, c)))
+                .map(|(r, c)| info[[r, c]].to_string())
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            writeln!(
+                writer,
+                "{} {} {} {} {}",
+                edge_tag,
+                self.ids[edge.i],
+                self.ids[edge.j],
+                format(&edge.measurement),
+                info
+            )?;
+        }
+
+        writer.flush()
+    }
+}
+
+/// The pose graph with the vertex ids in the file.
+struct RawPoseGraph {
+    vertices: Vec<(usize, Array2<f64>)>,
+    edges: Vec<(usize, usize, Array2<f64>, Array2<f64>)>,
+}
+
+impl RawPoseGraph {
+    fn into_graph(mut self, rotation_scale: f64) -> io::Result<PoseGraph> {
+        self.vertices.sort_by_key(|(id, _)| *id);
+
+        let index: HashMap<usize, usize> = self
+            .vertices
+            .iter()
+            .enumerate()
+            .map(|(k, (id, _))| (*id, k))
+            .collect();
+        if index.len() != self.vertices.len() {
+            return Err(invalid_data("Duplicated vertex ids"));
+        }
+
+        let mut edges = Vec::with_capacity(self.edges.len());
+        for (id_i, id_j, measurement, info) in self.edges.into_iter() {
+            let (i, j) = match (index.get(&id_i), index.get(&id_j)) {
+                (Some(i), Some(j)) => (*i, *j),
+                _ => {
+                    return Err(invalid_data(&format!(
+                        "Edge ({}, {}) refers to a missing vertex",
+                        id_i, id_j
+                    )))
+                }
+            };
+
+            let cov = info
+                .inv()
+                .map_err(|_| invalid_data("Information matrix must be invertible"))?;
+            let trace = |k: usize| (k..k + 3).map(|r| cov[[r, r]]).sum::<f64>();
+
+            let mut edge = Edge::new(i, j, measurement, id_j == id_i + 1);
+            edge.set_noise_bounds((rotation_scale * trace(3)).sqrt(), trace(0).sqrt());
+            edges.push(edge);
+        }
+
+        let (ids, poses) = self.vertices.into_iter().unzip();
+
+        Ok(PoseGraph { ids, poses, edges })
+    }
+}
+
+/// Reads the vertices and the edges of a pose graph file, where each line is
+/// a tag followed by ids, the `n_pose_values` values of the translation and
+/// rotation of the pose and (for edges) the upper triangular entries of the
+/// information matrix.
+fn read<P: AsRef<Path>, F: Fn(&[f64]) -> Array2<f64>>(
+    path: P,
+    vertex_tag: &str,
+    edge_tag: &str,
+    n_pose_values: usize,
+    to_pose: F,
+) -> io::Result<RawPoseGraph> {
+    let reader = BufReader::new(File::open(path)?);
+
+    let mut raw = RawPoseGraph {
+        vertices: Vec::new(),
+        edges: Vec::new(),
+    };
+
+    for line in reader.lines() {
+        let line = line?;
+        let tokens = line.split_whitespace().collect::<Vec<&str>>();
+        let tag = match tokens.first() {
+            Some(tag) => tag,
+            None => continue,
+        };
+
+        if *tag == vertex_tag {
+            let id = parse_id(&tokens, 1)?;
+            let values = parse_values(&tokens[2..], n_pose_values)?;
+            raw.vertices.push((id, to_pose(&values)));
+        } else if *tag == edge_tag {
+            let id_i = parse_id(&tokens, 1)?;
+            let id_j = parse_id(&tokens, 2)?;
+            let values = parse_values(&tokens[3..], n_pose_values + 21)?;
+
+            let mut info = Array2::<f64>::zeros((6, 6));
+            let mut k = n_pose_values;
+            for r in 0..6 {
+                for c in r..6 {
+                    info[[r, c]] = values[k];
+                    info[[c, r]] = values[k];
+                    k += 1;
+                }
+            }
+
+            raw.edges
+                .push((id_i, id_j, to_pose(&values[0..n_pose_values]), info));
+        }
+    }
+
+    Ok(raw)
+}
+
+fn parse_id(tokens: &[&str], k: usize) -> io::Result<usize> {
+    tokens
+        .get(k)
+        .and_then(|token| token.parse::<usize>().ok())
+        .ok_or_else(|| invalid_data(&format!("Invalid vertex id in '{}'", tokens.join(" "))))
+}
+
+fn parse_values(tokens: &[&str], n: usize) -> io::Result<Vec<f64>> {
+    if tokens.len() < n {
+        return Err(invalid_data(&format!(
+            "Expected {} values, got {}",
+            n,
+            tokens.len()
+        )));
+    }
+
+    tokens[0..n]
+        .iter()
+        .map(|token| {
+            token
+                .parse::<f64>()
+                .map_err(|_| invalid_data(&format!("Invalid value '{}'", token)))
+        })
+        .collect()
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg.to_string())
+}
+
+fn pose(translation: &[f64], rot: Array2<f64>) -> Array2<f64> {
+    let mut pose = Array2::<f64>::eye(4);
+    pose.slice_mut(s![0..3, 0..3]).assign(&rot);
+    pose.slice_mut(s![0..3, 3])
+        .assign(&ArrayView1::from(translation));
+
+    pose
+}
+
+/// Converts the Euler angles to the rotation matrix $R_z(\text{yaw})
+/// R_y(\text{pitch}) R_x(\text{roll})$.
+fn euler_to_rot_mat(roll: f64, pitch: f64, yaw: f64) -> Array2<f64> {
+    let (sr, cr) = roll.sin_cos();
+    let (sp, cp) = pitch.sin_cos();
+    let (sy, cy) = yaw.sin_cos();
+
+    array![
+        [cy * cp, cy * sp * sr - sy * cr, cy * sp * cr + sy * sr],
+        [sy * cp, sy * sp * sr + cy * cr, sy * sp * cr - cy * sr],
+        [-sp, cp * sr, cp * cr]
+    ]
+}
+
+/// Converts a rotation matrix to the Euler angles (roll, pitch, yaw), see
+/// [`euler_to_rot_mat`].
+fn rot_mat_to_euler(rot: &ArrayView2<f64>) -> (f64, f64, f64) {
+    let pitch = (-rot[[2, 0]]).clamp(-1.0, 1.0).asin();
+    let roll = rot[[2, 1]].atan2(rot[[2, 2]]);
+    let yaw = rot[[1, 0]].atan2(rot[[0, 0]]);
+
+    (roll, pitch, yaw)
+}
@@ -0,0 +1,195 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::Array2;
+
+use crate::manifold::{self, Manifold};
+use crate::pose_graph::utils::{self as pg_utils, Edge};
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sparse,
+};
+use crate::sparse::{self, CsrMatrix};
+use crate::utils;
+
+/// FracGM-based SE(3) pose-graph optimization solver with linear (naive)
+/// relaxation.
+///
+/// Given the relative poses $T_{ij}$ of the edges $(i, j)$ of a pose graph, it
+/// estimates the absolute poses $T_k$ of the nodes with the residuals $R_j -
+/// R_i R_{ij}$ and $t_j - t_i - R_i t_{ij}$, which are normalized by the noise
+/// bounds of each edge. Odometry edges are never rejected as outliers, so that
+/// only loop closures are robustified. The gauge freedom is removed by fixing
+/// the pose of the anchor node to the identity. The pose graph must be
+/// connected.
+///
+/// The subproblems are assembled and solved with the sparse backend of
+/// [`crate::sparse`], and their solutions are projected onto SE(3) node by
+/// node, as in [`crate::averaging::RotationSolver`]. As there, the projected
+/// iterates are not the solutions of the subproblems, so the convergence
+/// guarantee of FracGM does not apply and the solver is a FracGM-weighted
+/// heuristic.
+pub struct LinearSolver {
+    /// The number of nodes of the pose graph.
+    pub n_nodes: usize,
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The default noise bound of the rotation residuals (default: 0.1).
+    pub rotation_noise_bound: f64,
+    /// The default noise bound of the translation residuals (default: 0.1).
+    pub translation_noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    /// The node whose pose is fixed to the identity (default: 0).
+    pub anchor: usize,
+}
+
+impl LinearSolver {
+    /// Create a solver whose default noise bounds of the rotation and the
+    /// translation residuals are `noise_bound`. The noise bounds of an edge
+    /// override the defaults.
+    pub fn new(
+        n_nodes: usize,
+        max_iteration: usize,
+        tol: f64,
+        noise_bound: Option<f64>,
+        c: Option<f64>,
+    ) -> Self {
+        assert!(n_nodes > 1, "Pose graph must have at least two nodes");
+
+        let noise_bound = noise_bound.unwrap_or(0.1);
+
+        Self {
+            n_nodes,
+            max_iteration,
+            tol,
+            rotation_noise_bound: noise_bound,
+            translation_noise_bound: noise_bound,
+            c: c.unwrap_or(1.0),
+            anchor: 0,
+        }
+    }
+
+    pub fn set_rotation_noise_bound(&mut self, noise_bound: f64) {
+        self.rotation_noise_bound = noise_bound;
+    }
+
+    pub fn set_translation_noise_bound(&mut self, noise_bound: f64) {
+        self.translation_noise_bound = noise_bound;
+    }
+
+    pub fn set_anchor(&mut self, anchor: usize) {
+        assert!(anchor < self.n_nodes, "Anchor must be a node of the graph");
+        self.anchor = anchor;
+    }
+
+    /// Computes the Geman-McClure weight of each edge at the given poses,
+    /// which is close to 1 for inliers and vanishes for outliers. The weights
+    /// of odometry edges are 1.
+    ///
+    /// # Arguments
+    ///
+    /// - `edges` - The edges of the pose graph.
+    /// - `poses` - The absolute poses of the nodes.
+    ///
+    /// # Returns
+    ///
+    /// The weight of each edge.
+    pub fn edge_weights(&self, edges: &[Edge], poses: &Vec<Array2<f64>>) -> Vec<f64> {
+        let mut terms = self.compute_terms(&edges);
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(poses));
+
+        utils::compute_weights(&terms, self.c())
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sparse> for LinearSolver {
+    type Input<'a> = &'a [Edge];
+    type Output = Vec<Array2<f64>>;
+
+    fn dim(&self) -> usize {
+        pg_utils::BLOCK * (self.n_nodes - 1) + 1
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, poses: &Vec<Array2<f64>>) -> Array2<f64> {
+        pg_utils::poses_to_vec(poses, self.anchor)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Vec<Array2<f64>> {
+        pg_utils::vec_to_poses(vec, self.n_nodes, self.anchor)
+    }
+    fn project(&self, poses: &Vec<Array2<f64>>) -> Vec<Array2<f64>> {
+        poses
+            .iter()
+            .map(|pose| manifold::SE3.project(pose))
+            .collect()
+    }
+
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let vec = utils::solve_homogeneous(mat);
+
+        self.solve_x_sparse(&CsrMatrix::from_dense(mat), &vec)
+    }
+
+    fn use_sparse(&self) -> bool {
+        true
+    }
+
+    /// Solve the subproblem warm-started from the previous solution, and
+    /// project it onto SE(3) node by node. The translations are then solved
+    /// again with the projected rotations fixed, since the translations of the
+    /// relaxed solution are computed with rotations that are not orthogonal.
+    fn solve_x_sparse(&self, mat: &CsrMatrix, vec: &Array2<f64>) -> Array2<f64> {
+        let max_iteration = 10 * self.dim();
+
+        let vec = sparse::solve_homogeneous(mat, Some(vec), sparse::CG_TOL, max_iteration);
+        let vec = self.mat_to_vec(&self.project(&self.vec_to_mat(&vec)));
+
+        let translations = (0..self.dim() - 1)
+            .filter(|k| k % pg_utils::BLOCK >= 9)
+            .collect::<Vec<usize>>();
+
+        sparse::solve_free(mat, &vec, &translations, sparse::CG_TOL, max_iteration)
+    }
+
+    fn check_input(&self, edges: &Self::Input<'_>) {
+        pg_utils::check_edges(edges, self.n_nodes);
+    }
+
+    fn compute_terms(&self, edges: &Self::Input<'_>) -> Vec<Fractional<R2Sparse>> {
+        edges
+            .iter()
+            .map(|edge| {
+                pg_utils::compute_edge_term(
+                    edge,
+                    self.anchor,
+                    self.dim(),
+                    edge.rotation_noise_bound
+                        .unwrap_or(self.rotation_noise_bound),
+                    edge.translation_noise_bound
+                        .unwrap_or(self.translation_noise_bound),
+                    self.c(),
+                )
+            })
+            .collect()
+    }
+
+    fn compute_initial_guess(&self, edges: &Self::Input<'_>) -> Vec<Array2<f64>> {
+        pg_utils::compute_odometry_initial_guess(edges, self.n_nodes, self.anchor)
+    }
+}
+
+impl GemanMcclureSolver<R2Sparse> for LinearSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sparse> for LinearSolver {}
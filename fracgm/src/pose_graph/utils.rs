@@ -0,0 +1,251 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::collections::VecDeque;
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::averaging::utils::block_offset;
+use crate::registration::utils as reg_utils;
+use crate::solver::{Fractional, R2Sparse};
+
+/// The size of the block of a node in the lifted variable, which stacks
+/// $\mathrm{vec}(R)$ and $t$ of the pose.
+pub const BLOCK: usize = 12;
+
+/// A relative-pose edge $(i, j)$ of a pose graph.
+#[derive(Clone)]
+pub struct Edge {
+    /// The first node.
+    pub i: usize,
+    /// The second node.
+    pub j: usize,
+    /// The relative pose $T_{ij} = T_i^{-1} T_j$ as a 4x4 homogeneous matrix.
+    pub measurement: Array2<f64>,
+    /// The noise bound of the chordal rotation residual. The solver default
+    /// is used if it is `None`.
+    pub rotation_noise_bound: Option<f64>,
+    /// The noise bound of the translation residual. The solver default is
+    /// used if it is `None`.
+    pub translation_noise_bound: Option<f64>,
+    /// Whether the edge is an odometry edge, which is never an outlier.
+    pub odometry: bool,
+}
+
+impl Edge {
+    pub fn new(i: usize, j: usize, measurement: Array2<f64>, odometry: bool) -> Self {
+        Self {
+            i,
+            j,
+            measurement,
+            rotation_noise_bound: None,
+            translation_noise_bound: None,
+            odometry,
+        }
+    }
+
+    pub fn set_noise_bounds(&mut self, rotation_noise_bound: f64, translation_noise_bound: f64) {
+        self.rotation_noise_bound = Some(rotation_noise_bound);
+        self.translation_noise_bound = Some(translation_noise_bound);
+    }
+}
+
+/// Checks if the edges are valid for a pose graph with `n_nodes` nodes.
+///
+/// # Panics
+///
+/// Panics if an edge is a self-loop, refers to a missing node, has a
+/// measurement that is not 4x4, or has a non-positive noise bound.
+pub fn check_edges(edges: &[Edge], n_nodes: usize) {
+    for edge in edges.iter() {
+        assert!(
+            edge.i < n_nodes && edge.j < n_nodes,
+            "Edge ({}, {}) refers to a missing node",
+            edge.i,
+            edge.j
+        );
+        assert!(
+            edge.i != edge.j,
+            "Edge ({}, {}) must not be a self-loop",
+            edge.i,
+            edge.j
+        );
+        assert!(
+            edge.measurement.dim() == (4, 4),
+            "Edge measurements must be 4x4 matrices"
+        );
+        assert!(
+            edge.rotation_noise_bound.unwrap_or(1.0) > 0.0
+                && edge.translation_noise_bound.unwrap_or(1.0) > 0.0,
+            "Noise bounds of edge ({}, {}) must be positive",
+            edge.i,
+            edge.j
+        );
+    }
+}
+
+/// Computes the inverse of a 4x4 homogeneous transformation matrix.
+pub fn se3_inverse(pose: &Array2<f64>) -> Array2<f64> {
+    let rot_t = pose.slice(s![0..3, 0..3]).t().to_owned();
+    let trans: Array1<f64> = rot_t.dot(&pose.slice(s![0..3, 3]));
+
+    let mut inv = Array2::<f64>::eye(4);
+    inv.slice_mut(s![0..3, 3]).assign(&trans.mapv(|x| -x));
+    inv.slice_mut(s![0..3, 0..3]).assign(&rot_t);
+
+    inv
+}
+
+/// Computes the Geman-McClure term of an edge in the lifted variable that
+/// stacks $[\mathrm{vec}(R_k); t_k]$ of the nodes without the anchor, whose
+/// pose is fixed to the identity, and the homogeneous coordinate. The residual
+/// stacks the chordal rotation residual $R_j - R_i R_{ij}$ and the translation
+/// residual $t_j - t_i - R_i t_{ij}$, each normalized by its noise bound.
+/// Odometry edges lead to non-robust terms.
+pub fn compute_edge_term(
+    edge: &Edge,
+    anchor: usize,
+    dim: usize,
+    rotation_noise_bound: f64,
+    translation_noise_bound: f64,
+    c: f64,
+) -> Fractional<R2Sparse> {
+    let id3 = Array2::<f64>::eye(3);
+    let rot_ij = edge.measurement.slice(s![0..3, 0..3]);
+    let t_ij = edge.measurement.slice(s![0..3, 3]).to_owned();
+
+    // $\mathrm{vec}(R_i R_{ij}) = (R_{ij}^\top \otimes I) \mathrm{vec}(R_i)$
+    // and $R_i t_{ij} = (t_{ij}^\top \otimes I) \mathrm{vec}(R_i)$.
+    let mut mat_i = Array2::<f64>::zeros((BLOCK, BLOCK));
+    mat_i
+        .slice_mut(s![0..9, 0..9])
+        .assign(&kron(&rot_ij.t(), &id3).mapv(|x| -x));
+    mat_i
+        .slice_mut(s![9..12, 0..9])
+        .assign(&kron(&t_ij.into_shape((1, 3)).unwrap(), &id3).mapv(|x| -x));
+    mat_i.slice_mut(s![9..12, 9..12]).assign(&id3.mapv(|x| -x));
+    let mat_j = Array2::<f64>::eye(BLOCK);
+
+    let identity = reg_utils::se3_mat_to_vec(&Array2::eye(4));
+    let mut constant = Array1::<f64>::zeros(BLOCK);
+    let mut blocks = Vec::new();
+    for (node, mat) in [(edge.i, mat_i), (edge.j, mat_j)] {
+        match block_offset(node, anchor, BLOCK) {
+            Some(offset) => blocks.push((offset, mat)),
+            None => constant += &mat.dot(&identity.slice(s![0..BLOCK, 0])),
+        }
+    }
+
+    let k = BLOCK * blocks.len() + 1;
+    let mut mat_n = Array2::<f64>::zeros((BLOCK, k));
+    let mut indices = Vec::with_capacity(k);
+    for (b, (offset, mat)) in blocks.iter().enumerate() {
+        mat_n
+            .slice_mut(s![.., BLOCK * b..BLOCK * (b + 1)])
+            .assign(mat);
+        indices.extend(*offset..*offset + BLOCK);
+    }
+    mat_n.slice_mut(s![.., k - 1]).assign(&constant);
+    indices.push(dim - 1);
+
+    mat_n
+        .slice_mut(s![0..9, ..])
+        .mapv_inplace(|x| x / rotation_noise_bound);
+    mat_n
+        .slice_mut(s![9..12, ..])
+        .mapv_inplace(|x| x / translation_noise_bound);
+
+    let r2 = R2Sparse::new(mat_n.t().dot(&mat_n), indices);
+
+    match edge.odometry {
+        true => Fractional::new_non_robust(r2),
+        false => Fractional::new(r2, c),
+    }
+}
+
+/// Stacks the poses of the nodes without the anchor into the lifted variable.
+pub fn poses_to_vec(poses: &[Array2<f64>], anchor: usize) -> Array2<f64> {
+    let dim = BLOCK * (poses.len() - 1) + 1;
+
+    let mut vec = Array2::<f64>::ones((dim, 1));
+    for (node, pose) in poses.iter().enumerate() {
+        if let Some(offset) = block_offset(node, anchor, BLOCK) {
+            vec.slice_mut(s![offset..offset + BLOCK, ..])
+                .assign(&reg_utils::se3_mat_to_vec(pose).slice(s![0..BLOCK, ..]));
+        }
+    }
+
+    vec
+}
+
+/// Unstacks the lifted variable into the poses of all the nodes, where the
+/// pose of the anchor is the identity.
+pub fn vec_to_poses(vec: &Array2<f64>, n_nodes: usize, anchor: usize) -> Vec<Array2<f64>> {
+    (0..n_nodes)
+        .map(|node| match block_offset(node, anchor, BLOCK) {
+            Some(offset) => {
+                let mut block = Array2::<f64>::ones((reg_utils::DIM, 1));
+                block
+                    .slice_mut(s![0..BLOCK, ..])
+                    .assign(&vec.slice(s![offset..offset + BLOCK, ..]));
+                reg_utils::se3_vec_to_mat(&block)
+            }
+            None => Array2::eye(4),
+        })
+        .collect()
+}
+
+/// Computes the initial guess by composing the relative poses along a
+/// spanning tree rooted at the anchor. The tree uses as few loop closures as
+/// possible, i.e., it follows the odometry edges whenever they reach a node.
+///
+/// # Panics
+///
+/// Panics if the pose graph is not connected.
+pub fn compute_odometry_initial_guess(
+    edges: &[Edge],
+    n_nodes: usize,
+    anchor: usize,
+) -> Vec<Array2<f64>> {
+    let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); n_nodes];
+    for (k, edge) in edges.iter().enumerate() {
+        adjacency[edge.i].push(k);
+        adjacency[edge.j].push(k);
+    }
+
+    // 0-1 BFS, where odometry edges have weight 0 and loop closures weight 1.
+    let mut poses: Vec<Option<Array2<f64>>> = vec![None; n_nodes];
+    let mut queue: VecDeque<(usize, Array2<f64>)> = VecDeque::new();
+    queue.push_back((anchor, Array2::eye(4)));
+
+    while let Some((node, pose)) = queue.pop_front() {
+        if poses[node].is_some() {
+            continue;
+        }
+
+        for &k in adjacency[node].iter() {
+            let edge = &edges[k];
+            let (next, next_pose) = match node == edge.i {
+                true => (edge.j, pose.dot(&edge.measurement)),
+                false => (edge.i, pose.dot(&se3_inverse(&edge.measurement))),
+            };
+            if poses[next].is_some() {
+                continue;
+            }
+            match edge.odometry {
+                true => queue.push_front((next, next_pose)),
+                false => queue.push_back((next, next_pose)),
+            }
+        }
+
+        poses[node] = Some(pose);
+    }
+
+    poses
+        .into_iter()
+        .map(|pose| pose.expect("Pose graph must be connected"))
+        .collect()
+}
@@ -0,0 +1,24 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solvers of robust geometric primitive
+//! fitting, i.e., planes and lines in 3D and hyperspheres (e.g., circles in 2D
+//! and spheres in 3D). The residuals are linear in the parameters of the
+//! primitives under a normalization constraint: the unit normal of planes (and
+//! of the two planes whose intersection is a line), and the unit coefficient
+//! of $\|p\|^2$ in the algebraic residual $\|p - c\|^2 - r^2$ of
+//! hyperspheres.
+//!
+//! Besides the primitive, each solver reports the Geman-McClure weight of
+//! each point and the residual RMS, see [`PrimitiveFit`].
+
+pub mod hypersphere;
+pub mod line;
+pub mod plane;
+pub mod utils;
+
+pub use hypersphere::{Hypersphere, HypersphereSolver};
+pub use line::{Line, LineSolver};
+pub use plane::{Plane, PlaneSolver};
+pub use utils::PrimitiveFit;
@@ -0,0 +1,153 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::primitive::utils::{self as prim_utils, PrimitiveFit};
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// A hypersphere $\{p : \|p - c\| = r\}$, e.g., a circle in 2D or a
+/// sphere in 3D.
+#[derive(Clone)]
+pub struct Hypersphere {
+    /// The center $c$.
+    pub center: Array1<f64>,
+    /// The radius $r$.
+    pub radius: f64,
+}
+
+impl Hypersphere {
+    /// Get the distance of a point to the hypersphere.
+    pub fn distance(&self, point: &ArrayView1<f64>) -> f64 {
+        let diff = point - &self.center;
+
+        (diff.dot(&diff).sqrt() - self.radius).abs()
+    }
+}
+
+/// FracGM-based hypersphere (circle or sphere) fitting solver.
+///
+/// The algebraic residual $\|p - c\|^2 - r^2$ is linear in the lifted
+/// variable $[c; \|c\|^2 - r^2; 1]$, see [`prim_utils::sphere_mat`]. It is
+/// about $2r$ times the distance of the point $p$ to the hypersphere, so the
+/// noise bound is in the units of the squared points.
+pub struct HypersphereSolver {
+    /// The dimension of the points, e.g., 2 for circles and 3 for spheres.
+    pub n_dims: usize,
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl HypersphereSolver {
+    pub fn new(
+        n_dims: usize,
+        max_iteration: usize,
+        tol: f64,
+        noise_bound: Option<f64>,
+        c: Option<f64>,
+    ) -> Self {
+        assert!(n_dims > 0, "Points must have at least one dimension");
+
+        Self {
+            n_dims,
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Fit a hypersphere to the points, and report the weight of each point
+    /// and the residual RMS.
+    ///
+    /// # Arguments
+    ///
+    /// - `points` - The points with shape `(n, n_dims)`.
+    ///
+    /// # Returns
+    ///
+    /// The fitted hypersphere with the weights and the residual RMS.
+    pub fn fit(&self, points: &Array2<f64>) -> PrimitiveFit<Hypersphere> {
+        let sphere = GemanMcclureSolver::solve(self, points);
+
+        let mut terms = self.compute_terms(&points);
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&sphere));
+
+        let distances = points
+            .rows()
+            .into_iter()
+            .map(|point| sphere.distance(&point))
+            .collect::<Vec<f64>>();
+
+        PrimitiveFit::new(sphere, utils::compute_weights(&terms, self.c()), &distances)
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for HypersphereSolver {
+    type Input<'a> = &'a Array2<f64>;
+    type Output = Hypersphere;
+
+    fn dim(&self) -> usize {
+        self.n_dims + 2
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, sphere: &Hypersphere) -> Array2<f64> {
+        let d = self.n_dims;
+        let center = &sphere.center;
+
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..d, 0]).assign(center);
+        vec[[d, 0]] = center.dot(center) - sphere.radius * sphere.radius;
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Hypersphere {
+        let d = self.n_dims;
+
+        let center: Array1<f64> = vec.slice(s![0..d, 0]).to_owned();
+        let radius = (center.dot(&center) - vec[[d, 0]]).max(0.0).sqrt();
+
+        Hypersphere { center, radius }
+    }
+    fn project(&self, sphere: &Hypersphere) -> Hypersphere {
+        sphere.clone()
+    }
+
+    fn check_input(&self, points: &Self::Input<'_>) {
+        prim_utils::check_points(points, self.n_dims, self.n_dims + 1);
+    }
+
+    fn compute_terms(&self, points: &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        prim_utils::compute_terms(points, self.noise_bound, self.c(), prim_utils::sphere_mat)
+    }
+
+    /// The algebraic least squares hypersphere of all the points.
+    fn compute_initial_guess(&self, points: &Self::Input<'_>) -> Hypersphere {
+        self.compute_least_squares_initial_guess(&self.compute_terms(points))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for HypersphereSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for HypersphereSolver {}
@@ -0,0 +1,212 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::linalg::kron;
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::primitive::utils::{self as prim_utils, PrimitiveFit};
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// The dimension of the points.
+const N_DIMS: usize = 3;
+/// The number of planes whose intersection is a line.
+const N_PLANES: usize = N_DIMS - 1;
+/// The number of entries of $[N, D]$.
+const N_PARAMS: usize = N_PLANES * (N_DIMS + 1);
+
+/// A line $\{p_0 + s v : s \in \mathbb{R}\}$ in 3D.
+#[derive(Clone)]
+pub struct Line {
+    /// The point $p_0$ of the line closest to the origin.
+    pub point: Array1<f64>,
+    /// The unit direction $v$.
+    pub direction: Array1<f64>,
+}
+
+impl Line {
+    /// Get the distance of a point to the line.
+    pub fn distance(&self, point: &ArrayView1<f64>) -> f64 {
+        let diff = point - &self.point;
+        let along = diff.dot(&self.direction);
+
+        (diff.dot(&diff) - along * along).max(0.0).sqrt()
+    }
+}
+
+/// FracGM-based line fitting solver.
+///
+/// A line is the intersection of two planes $N p + D = 0$, where the rows of
+/// the $2 \times 3$ matrix $N$ are orthonormal and orthogonal to the
+/// direction. The residual $N p + D$ is the offset of the point $p$ from the
+/// line, so the noise bound is in the units of the points. The lifted variable
+/// is $[\mathrm{vec}([N, D]); 1]$, and each subproblem is solved in closed form
+/// by [`prim_utils::fit_subspace`].
+pub struct LineSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl LineSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Fit a line to the points, and report the weight of each point and the
+    /// residual RMS.
+    ///
+    /// # Arguments
+    ///
+    /// - `points` - The points with shape `(n, 3)`.
+    ///
+    /// # Returns
+    ///
+    /// The fitted line with the weights and the residual RMS.
+    pub fn fit(&self, points: &Array2<f64>) -> PrimitiveFit<Line> {
+        let line = GemanMcclureSolver::solve(self, points);
+
+        let mut terms = self.compute_terms(&points);
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&line));
+
+        let distances = points
+            .rows()
+            .into_iter()
+            .map(|point| line.distance(&point))
+            .collect::<Vec<f64>>();
+
+        PrimitiveFit::new(line, utils::compute_weights(&terms, self.c()), &distances)
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for LineSolver {
+    type Input<'a> = &'a Array2<f64>;
+    type Output = Line;
+
+    fn dim(&self) -> usize {
+        N_PARAMS + 1
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, line: &Line) -> Array2<f64> {
+        let v = &line.direction;
+
+        // Complete the direction to an orthonormal basis $(v, n_1, n_2)$.
+        let axis = (0..N_DIMS)
+            .min_by(|&a, &b| v[a].abs().partial_cmp(&v[b].abs()).unwrap())
+            .unwrap();
+        let mut e = Array1::<f64>::zeros(N_DIMS);
+        e[axis] = 1.0;
+        let n1 = cross(v, &e);
+        let n1 = &n1 / n1.dot(&n1).sqrt();
+        let n2 = cross(v, &n1);
+
+        let mut mat = Array2::<f64>::zeros((N_PLANES, N_DIMS + 1));
+        for (j, normal) in [n1, n2].iter().enumerate() {
+            mat.slice_mut(s![j, 0..N_DIMS]).assign(normal);
+            mat[[j, N_DIMS]] = -normal.dot(&line.point);
+        }
+
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..N_PARAMS, 0])
+            .assign(&mat.t().iter().cloned().collect::<Array1<f64>>());
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Line {
+        let mut mat = vec
+            .slice(s![0..N_PARAMS, 0])
+            .to_owned()
+            .into_shape((N_DIMS + 1, N_PLANES))
+            .unwrap();
+        mat.swap_axes(0, 1);
+
+        let normals = mat.slice(s![.., 0..N_DIMS]);
+        let direction = cross(&normals.row(0).to_owned(), &normals.row(1).to_owned());
+
+        Line {
+            point: normals.t().dot(&mat.column(N_DIMS)).mapv(|x| -x),
+            direction,
+        }
+    }
+    fn project(&self, line: &Line) -> Line {
+        let direction = &line.direction / line.direction.dot(&line.direction).sqrt();
+        let point = &line.point - &(line.point.dot(&direction) * &direction);
+
+        Line { point, direction }
+    }
+
+    /// The residuals have no constant term, so the subproblem is solved with
+    /// orthonormal $N$ instead of the homogeneous coordinate. The matrix of
+    /// the subproblem is $S \otimes I_2$ with the moment matrix $S$ of the
+    /// points.
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let moment = Array2::from_shape_fn((N_DIMS + 1, N_DIMS + 1), |(a, b)| {
+            mat[[N_PLANES * a, N_PLANES * b]]
+        });
+        let planes = prim_utils::fit_subspace(&moment, N_PLANES);
+
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..N_PARAMS, 0])
+            .assign(&planes.t().iter().cloned().collect::<Array1<f64>>());
+
+        vec
+    }
+
+    fn check_input(&self, points: &Self::Input<'_>) {
+        prim_utils::check_points(points, N_DIMS, 2);
+    }
+
+    fn compute_terms(&self, points: &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        prim_utils::compute_terms(points, self.noise_bound, self.c(), |point| {
+            let mut homogeneous = Array2::<f64>::ones((1, N_DIMS + 1));
+            homogeneous.slice_mut(s![0, 0..N_DIMS]).assign(point);
+
+            let mut mat = Array2::<f64>::zeros((N_PLANES, self.dim()));
+            mat.slice_mut(s![.., 0..N_PARAMS])
+                .assign(&kron(&homogeneous, &Array2::eye(N_PLANES)));
+
+            mat
+        })
+    }
+
+    /// The least squares line of all the points.
+    fn compute_initial_guess(&self, points: &Self::Input<'_>) -> Line {
+        self.compute_least_squares_initial_guess(&self.compute_terms(points))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for LineSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for LineSolver {}
+
+fn cross(a: &Array1<f64>, b: &Array1<f64>) -> Array1<f64> {
+    array![
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0]
+    ]
+}
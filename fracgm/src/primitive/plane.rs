@@ -0,0 +1,159 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::primitive::utils::{self as prim_utils, PrimitiveFit};
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// The dimension of the points.
+const N_DIMS: usize = 3;
+
+/// A plane $\{p : n^\top p + d = 0\}$ in 3D.
+#[derive(Clone)]
+pub struct Plane {
+    /// The unit normal $n$.
+    pub normal: Array1<f64>,
+    /// The offset $d$.
+    pub offset: f64,
+}
+
+impl Plane {
+    /// Get the distance of a point to the plane.
+    pub fn distance(&self, point: &ArrayView1<f64>) -> f64 {
+        (self.normal.dot(point) + self.offset).abs()
+    }
+}
+
+/// FracGM-based plane fitting solver.
+///
+/// The residual $n^\top p + d$ with $\|n\| = 1$ is the signed distance of the
+/// point $p$ to the plane, so the noise bound is in the units of the points.
+/// The lifted variable is $[n; d; 1]$, and each subproblem is solved in
+/// closed form by [`prim_utils::fit_subspace`].
+pub struct PlaneSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl PlaneSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Fit a plane to the points, and report the weight of each point and the
+    /// residual RMS.
+    ///
+    /// # Arguments
+    ///
+    /// - `points` - The points with shape `(n, 3)`.
+    ///
+    /// # Returns
+    ///
+    /// The fitted plane with the weights and the residual RMS.
+    pub fn fit(&self, points: &Array2<f64>) -> PrimitiveFit<Plane> {
+        let plane = GemanMcclureSolver::solve(self, points);
+
+        let mut terms = self.compute_terms(&points);
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&plane));
+
+        let distances = points
+            .rows()
+            .into_iter()
+            .map(|point| plane.distance(&point))
+            .collect::<Vec<f64>>();
+
+        PrimitiveFit::new(plane, utils::compute_weights(&terms, self.c()), &distances)
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for PlaneSolver {
+    type Input<'a> = &'a Array2<f64>;
+    type Output = Plane;
+
+    fn dim(&self) -> usize {
+        N_DIMS + 2
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, plane: &Plane) -> Array2<f64> {
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..N_DIMS, 0]).assign(&plane.normal);
+        vec[[N_DIMS, 0]] = plane.offset;
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Plane {
+        Plane {
+            normal: vec.slice(s![0..N_DIMS, 0]).to_owned(),
+            offset: vec[[N_DIMS, 0]],
+        }
+    }
+    fn project(&self, plane: &Plane) -> Plane {
+        let norm = plane.normal.dot(&plane.normal).sqrt();
+
+        Plane {
+            normal: &plane.normal / norm,
+            offset: plane.offset / norm,
+        }
+    }
+
+    /// The residuals have no constant term, so the subproblem is solved with
+    /// $\|n\| = 1$ instead of the homogeneous coordinate.
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let m = N_DIMS + 1;
+        let plane = prim_utils::fit_subspace(&mat.slice(s![0..m, 0..m]).to_owned(), 1);
+
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..m, 0]).assign(&plane.row(0));
+
+        vec
+    }
+
+    fn check_input(&self, points: &Self::Input<'_>) {
+        prim_utils::check_points(points, N_DIMS, N_DIMS);
+    }
+
+    fn compute_terms(&self, points: &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        prim_utils::compute_terms(
+            points,
+            self.noise_bound,
+            self.c(),
+            prim_utils::hyperplane_mat,
+        )
+    }
+
+    /// The least squares plane of all the points.
+    fn compute_initial_guess(&self, points: &Self::Input<'_>) -> Plane {
+        self.compute_least_squares_initial_guess(&self.compute_terms(points))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for PlaneSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for PlaneSolver {}
@@ -0,0 +1,159 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::{Eigh, UPLO};
+
+use crate::solver::{Fractional, R2Sym};
+
+/// The result of fitting a primitive.
+#[derive(Clone)]
+pub struct PrimitiveFit<T> {
+    /// The fitted primitive.
+    pub primitive: T,
+    /// The Geman-McClure weight of each point, which is close to 1 for
+    /// inliers and vanishes for outliers.
+    pub weights: Vec<f64>,
+    /// The root mean square of the distances of the points to the primitive,
+    /// weighted by the Geman-McClure weights.
+    pub rms: f64,
+}
+
+impl<T> PrimitiveFit<T> {
+    /// Creates a new instance of `PrimitiveFit` from the weights and the
+    /// distances of the points to the primitive.
+    pub fn new(primitive: T, weights: Vec<f64>, distances: &[f64]) -> Self {
+        assert!(
+            weights.len() == distances.len(),
+            "Size mismatch of weights and distances"
+        );
+
+        let total = weights.iter().sum::<f64>();
+        let rms = match total > 0.0 {
+            true => {
+                let sum = weights
+                    .iter()
+                    .zip(distances.iter())
+                    .map(|(w, d)| w * d * d)
+                    .sum::<f64>();
+                (sum / total).sqrt()
+            }
+            false => 0.0,
+        };
+
+        Self {
+            primitive,
+            weights,
+            rms,
+        }
+    }
+}
+
+/// Checks if the points are valid for fitting a primitive.
+///
+/// # Panics
+///
+/// Panics if the points do not have `n_cols` columns or are fewer than
+/// `min_points`.
+pub fn check_points(points: &Array2<f64>, n_cols: usize, min_points: usize) {
+    assert!(
+        points.ncols() == n_cols,
+        "Input points must have {} columns",
+        n_cols
+    );
+    assert!(
+        points.nrows() >= min_points,
+        "At least {} points are required",
+        min_points
+    );
+}
+
+/// Computes the Geman-McClure terms of the residuals $N_i x$, where the rows
+/// of the residual matrices $N_i$ are given by `residual_mat`.
+pub fn compute_terms<F>(
+    points: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+    residual_mat: F,
+) -> Vec<Fractional<R2Sym>>
+where
+    F: Fn(&ArrayView1<f64>) -> Array2<f64>,
+{
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(points.nrows());
+
+    for point in points.rows() {
+        let mat_n = residual_mat(&point);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Computes the $1 \times (d + 2)$ matrix $N$ of the residual $n^\top p + d$
+/// in the lifted variable $[n; d; 1]$.
+pub fn hyperplane_mat(point: &ArrayView1<f64>) -> Array2<f64> {
+    let dim = point.len();
+
+    let mut mat = Array2::<f64>::zeros((1, dim + 2));
+    mat.slice_mut(s![0, 0..dim]).assign(point);
+    mat[[0, dim]] = 1.0;
+
+    mat
+}
+
+/// Computes the $1 \times (d + 2)$ matrix $N$ of the algebraic residual
+/// $\|p\|^2 - 2 c^\top p + k$ of a sphere in the lifted variable $[c; k; 1]$,
+/// where $k = \|c\|^2 - r^2$. It equals $\|p - c\|^2 - r^2$, which is about
+/// $2r$ times the distance of $p$ to the sphere.
+pub fn sphere_mat(point: &ArrayView1<f64>) -> Array2<f64> {
+    let dim = point.len();
+
+    let mut mat = Array2::<f64>::zeros((1, dim + 2));
+    mat.slice_mut(s![0, 0..dim])
+        .assign(&point.mapv(|x| -2.0 * x));
+    mat[[0, dim]] = 1.0;
+    mat[[0, dim + 1]] = point.dot(point);
+
+    mat
+}
+
+/// Minimizes $\mathrm{tr}(X S X^\top)$ over $X = [N, d]$ with $k$
+/// orthonormal rows of $N$, where $S$ is the (weighted) second moment matrix
+/// of the points $[p; 1]$. The offsets $d$ are eliminated with the Schur
+/// complement, and the rows of $N$ are the eigenvectors of the $k$ smallest
+/// eigenvalues of the weighted scatter matrix of the points.
+///
+/// # Arguments
+///
+/// - `moment` - The moment matrix $S$ with shape `(m + 1, m + 1)`.
+/// - `k` - The number of rows of $X$.
+///
+/// # Returns
+///
+/// The matrix $X$ with shape `(k, m + 1)`.
+pub fn fit_subspace(moment: &Array2<f64>, k: usize) -> Array2<f64> {
+    let m = moment.nrows() - 1;
+    assert!(k <= m, "Subspace must have at most {} normals", m);
+
+    let s_nd = moment.slice(s![0..m, m]).to_owned();
+    let s_dd = moment[[m, m]];
+    assert!(s_dd > 0.0, "Points must have positive weights");
+
+    let s_nd_col = s_nd.view().into_shape((m, 1)).unwrap();
+    let scatter = &moment.slice(s![0..m, 0..m]) - &(s_nd_col.dot(&s_nd_col.t()) / s_dd);
+    let (_, eigvecs) = scatter.eigh(UPLO::Lower).unwrap();
+
+    let mut mat = Array2::<f64>::zeros((k, m + 1));
+    for j in 0..k {
+        let normal = eigvecs.column(j);
+        mat.slice_mut(s![j, 0..m]).assign(&normal);
+        mat[[j, m]] = -normal.dot(&s_nd) / s_dd;
+    }
+
+    mat
+}
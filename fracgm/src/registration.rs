@@ -2,8 +2,30 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+pub mod affine;
 pub mod decoupled;
+pub mod gravity_aligned;
+pub mod heterogeneous;
+pub mod line_to_line;
 pub mod linear;
+pub mod paired;
+pub mod planar;
+pub mod plane_to_plane;
+pub mod point_to_plane;
+pub mod quaternion;
+pub mod sdp;
+pub mod similarity;
 pub mod utils;
 
+pub use affine::AffineSolver;
+pub use gravity_aligned::GravityAlignedSolver;
+pub use heterogeneous::{Correspondence, HeterogeneousSolver};
+pub use line_to_line::{LineToLineSolver, Lines};
 pub use linear::LinearSolver;
+pub use paired::{PairKind, PairedSolver};
+pub use planar::PlanarSolver;
+pub use plane_to_plane::{PlaneToPlaneSolver, Planes};
+pub use point_to_plane::PointToPlaneSolver;
+pub use quaternion::QuaternionSolver;
+pub use sdp::SdpSolver;
+pub use similarity::SimilaritySolver;
@@ -0,0 +1,141 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::SVD;
+
+use crate::manifold::{Euclidean, Manifold};
+use crate::registration::utils as reg_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based 3D affine registration solver, i.e., $q = A p + t$ with a
+/// general 3x3 matrix $A$.
+///
+/// The lifted variable $[\mathrm{vec}(A); t; 1]$ shares the Geman-McClure
+/// terms of [`crate::registration::LinearSolver`]. Since every lifted variable
+/// is a feasible affine map, the linear relaxation is exact and the solution
+/// is not projected. The affine map is determined only if the inliers span
+/// the space, see [`AffineSolver::condition_numbers`].
+pub struct AffineSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl AffineSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Computes the condition numbers of an affine map. The first one is the
+    /// condition number of the linear part $A$, which measures how far the map
+    /// is from a similarity. The second one is the condition number of the
+    /// normal matrix $\sum_i w_i N_i^\top N_i$ of the Geman-McClure weighted
+    /// least squares problem at the map (without the homogeneous coordinate),
+    /// which is large if the inliers are degenerate, e.g., coplanar.
+    ///
+    /// # Arguments
+    ///
+    /// - `pc1` - The source point cloud with shape `(n, 3)`.
+    /// - `pc2` - The target point cloud with shape `(n, 3)`.
+    /// - `affine` - The 4x4 affine map.
+    ///
+    /// # Returns
+    ///
+    /// The condition numbers of the linear part and of the normal matrix.
+    pub fn condition_numbers(
+        &self,
+        pc1: &Array2<f64>,
+        pc2: &Array2<f64>,
+        affine: &Array2<f64>,
+    ) -> (f64, f64) {
+        let mut terms = self.compute_terms(&(pc1, pc2));
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(affine));
+
+        let n = self.dim() - 1;
+        let mut normal = Array2::<f64>::zeros((n, n));
+        for (term, w) in terms.iter().zip(utils::compute_weights(&terms, self.c())) {
+            normal.scaled_add(w, &term.h_mat().slice(s![0..n, 0..n]));
+        }
+
+        (
+            condition_number(&affine.slice(s![0..3, 0..3]).to_owned()),
+            condition_number(&normal),
+        )
+    }
+}
+
+/// Computes the ratio of the largest and the smallest singular values, which
+/// is infinite for singular matrices.
+fn condition_number(mat: &Array2<f64>) -> f64 {
+    let (_, sigma, _) = mat.svd(false, false).unwrap();
+
+    let max = sigma.iter().cloned().fold(0.0, f64::max);
+    let min = sigma.iter().cloned().fold(f64::INFINITY, f64::min);
+
+    match min > 0.0 {
+        true => max / min,
+        false => f64::INFINITY,
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for AffineSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        Euclidean.project(mat)
+    }
+
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, 3);
+    }
+
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        reg_utils::compute_terms(pc1, pc2, self.noise_bound, self.c())
+    }
+
+    /// The least squares affine map of all the correspondences.
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        self.compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for AffineSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for AffineSolver {}
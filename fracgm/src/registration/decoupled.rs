@@ -6,6 +6,8 @@ use ndarray::prelude::*;
 use ndarray::Array2;
 
 use crate::rotation::LinearSolver as RotationSolver;
+use crate::rotation::PlanarSolver as PlanarRotationSolver;
+use crate::scale::LinearSolver as ScaleSolver;
 use crate::solver::GemanMcclureSolver;
 use crate::translation::LinearSolver as TranslationSolver;
 use crate::utils;
@@ -17,9 +19,14 @@ pub enum TIMPolicy {
 
 pub struct Solver {
     pub rotation_solver: RotationSolver,
+    pub planar_rotation_solver: PlanarRotationSolver,
     pub translation_solver: TranslationSolver,
+    pub scale_solver: ScaleSolver,
 
     pub tim_policy: TIMPolicy,
+    /// Whether to estimate the scale, i.e., to solve a similarity
+    /// transformation instead of a rigid one (default: false).
+    pub estimate_scaling: bool,
 }
 
 impl Solver {
@@ -33,20 +40,56 @@ impl Solver {
             c,
         );
 
+        let planar_rotation_solver = PlanarRotationSolver::new(
+            max_iteration,
+            tol,
+            Some(translation_solver.noise_bound * 2.0),
+            c,
+        );
+
+        let scale_solver = ScaleSolver::new(
+            max_iteration,
+            tol,
+            Some(translation_solver.noise_bound * 2.0),
+            c,
+        );
+
         Self {
             rotation_solver,
+            planar_rotation_solver,
             translation_solver,
+            scale_solver,
             tim_policy: TIMPolicy::CHAIN,
+            estimate_scaling: false,
         }
     }
 
     pub fn set_tim_policy(&mut self, tim_policy: TIMPolicy) {
         self.tim_policy = tim_policy;
     }
+
+    pub fn set_estimate_scaling(&mut self, estimate_scaling: bool) {
+        self.estimate_scaling = estimate_scaling;
+    }
 }
 
 impl Solver {
+    /// Solve the registration problem in 3D, or in 2D if the point clouds have
+    /// two columns. If `estimate_scaling` is set, the scale is estimated from
+    /// the lengths of TIMs before the rotation, and falls back to 1 if it is
+    /// not positive.
+    ///
+    /// # Returns
+    ///
+    /// The homogeneous transformation matrix (4x4 in 3D and 3x3 in 2D), whose
+    /// top-left block is $sR$ if the scale is estimated.
     pub fn solve(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+        let n_dims = pc1.dim().1;
+        assert!(
+            n_dims == 2 || n_dims == 3,
+            "Input point clouds must have 2 or 3 columns"
+        );
+
         let pc1_tims = match self.tim_policy {
             TIMPolicy::CHAIN => utils::compute_chain_translation_invariant_measurements(pc1),
             TIMPolicy::COMPLETE => utils::compute_complete_translation_invariant_measurements(pc1),
@@ -56,14 +99,41 @@ impl Solver {
             TIMPolicy::COMPLETE => utils::compute_complete_translation_invariant_measurements(pc2),
         };
 
-        let rot = self.rotation_solver.solve(&pc1_tims, &pc2_tims);
+        let scale = if self.estimate_scaling {
+            self.scale_solver.solve((&pc1_tims, &pc2_tims))
+        } else {
+            1.0
+        };
+        // A degenerate scale (e.g., all the TIMs of `pc1` vanish) falls back
+        // to a rigid transformation.
+        let scale = if scale.is_finite() && scale > 0.0 {
+            scale
+        } else {
+            1.0
+        };
+        let pc1_tims = scale * pc1_tims;
+
+        let rot = if n_dims == 2 {
+            self.planar_rotation_solver.solve((&pc1_tims, &pc2_tims))
+        } else {
+            self.rotation_solver.solve((&pc1_tims, &pc2_tims))
+        };
+
+        let translation_solver = TranslationSolver {
+            n_dims,
+            ..self.translation_solver
+        };
+
+        let rot = scale * rot;
 
         let rot_pc1 = rot.dot(&pc1.t()).t().to_owned();
-        let trans = self.translation_solver.solve(&rot_pc1, pc2);
+        let trans = translation_solver.solve((&rot_pc1, pc2));
 
-        let mut transform = Array2::<f64>::eye(4);
-        transform.slice_mut(s![0..3, 0..3]).assign(&rot);
-        transform.slice_mut(s![0..3, 3..4]).assign(&trans);
+        let mut transform = Array2::<f64>::eye(n_dims + 1);
+        transform.slice_mut(s![0..n_dims, 0..n_dims]).assign(&rot);
+        transform
+            .slice_mut(s![0..n_dims, n_dims..n_dims + 1])
+            .assign(&trans);
 
         transform
     }
@@ -0,0 +1,105 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{array, Array1, Array2};
+
+use crate::certificate::Certifiable;
+use crate::registration::utils as reg_utils;
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based gravity-aligned (4-DoF) registration solver with linear (naive)
+/// relaxation.
+///
+/// The roll and pitch are assumed to be known (e.g., from an IMU), so only the
+/// yaw about the gravity axis and the 3D translation are estimated. The
+/// transformation is lifted to $[\cos\theta; \sin\theta; t; 1]$.
+pub struct GravityAlignedSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    /// The unit gravity axis (default: the z-axis).
+    pub gravity: Array1<f64>,
+}
+
+impl GravityAlignedSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+            gravity: array![0.0, 0.0, 1.0],
+        }
+    }
+
+    /// Set the gravity axis. The axis is normalized to unit length.
+    pub fn set_gravity(&mut self, gravity: &Array1<f64>) {
+        assert!(gravity.len() == 3, "Gravity axis must have 3 entries");
+
+        let norm = gravity.dot(gravity).sqrt();
+        assert!(norm > 0.0, "Gravity axis must be nonzero");
+
+        self.gravity = gravity / norm;
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for GravityAlignedSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::YAW_DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::yaw_mat_to_vec(mat, &self.gravity)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::yaw_vec_to_mat(vec, &self.gravity)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::yaw_project(mat, &self.gravity)
+    }
+
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, 3);
+    }
+
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        reg_utils::compute_yaw_terms(pc1, pc2, &self.gravity, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, (pc1, pc2): &Self::Input<'_>) -> Array2<f64> {
+        reg_utils::compute_yaw_initial_guess(pc1, pc2, &self.gravity)
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for GravityAlignedSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for GravityAlignedSolver {}
+
+impl Certifiable<R2Sym> for GravityAlignedSolver {
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        rot_utils::so2_constraints(reg_utils::YAW_DIM, 0)
+    }
+}
@@ -0,0 +1,209 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{Array1, Array2};
+
+use crate::certificate::Certifiable;
+use crate::manifold::{self, Manifold};
+use crate::registration::utils as reg_utils;
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+
+/// A typed correspondence between the source and the target frames.
+#[derive(Clone)]
+pub enum Correspondence {
+    /// A pair of points $p$ and $q$.
+    Point(Array1<f64>, Array1<f64>),
+    /// A pair of planes $[n; d]$ of the set $\{x : n^\top x + d = 0\}$ with unit
+    /// normal $n$.
+    Plane(Array1<f64>, Array1<f64>),
+    /// A pair of lines $[u; m]$ in Plücker coordinates with unit direction $u$.
+    Line(Array1<f64>, Array1<f64>),
+}
+
+impl Correspondence {
+    /// Computes the matrix $N$ such that $N x$ is the residual of the
+    /// correspondence, where $x = [\mathrm{vec}(R); t; 1]$.
+    pub fn residual_mat(&self) -> Array2<f64> {
+        match self {
+            Correspondence::Point(p, q) => reg_utils::point_to_point_mat(&p.view(), &q.view()),
+            Correspondence::Plane(plane1, plane2) => {
+                reg_utils::plane_to_plane_mat(&plane1.view(), &plane2.view())
+            }
+            Correspondence::Line(line1, line2) => {
+                reg_utils::line_to_line_mat(&line1.view(), &line2.view())
+            }
+        }
+    }
+}
+
+/// FracGM-based heterogeneous registration solver with linear (naive)
+/// relaxation.
+///
+/// Point, plane and line correspondences are fused in a single Geman-McClure
+/// objective. The residuals of all types are linear in the lifted variable
+/// $[\mathrm{vec}(R); t; 1]$, and each type is normalized by its own noise
+/// bounds, so that they share one set of fractional terms. As in
+/// [`crate::registration::PairedSolver`], the direction rows of planes and
+/// lines are normalized by their direction noise bounds, and the offset and
+/// moment rows by their offset noise bounds.
+pub struct HeterogeneousSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) of point correspondences (default: 0.1).
+    pub point_noise_bound: f64,
+    /// The noise bound (sigma) of the normal residuals of plane
+    /// correspondences (default: 0.1).
+    pub plane_direction_noise_bound: f64,
+    /// The noise bound (sigma) of the offset residuals of plane
+    /// correspondences (default: 0.1).
+    pub plane_offset_noise_bound: f64,
+    /// The noise bound (sigma) of the direction residuals of line
+    /// correspondences (default: 0.1).
+    pub line_direction_noise_bound: f64,
+    /// The noise bound (sigma) of the moment residuals of line
+    /// correspondences (default: 0.1).
+    pub line_offset_noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl HeterogeneousSolver {
+    /// Create a solver whose noise bounds of all correspondence types and
+    /// rows are `noise_bound`. Use the setters to assign them individually.
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        let noise_bound = noise_bound.unwrap_or(0.1);
+
+        Self {
+            max_iteration,
+            tol,
+            point_noise_bound: noise_bound,
+            plane_direction_noise_bound: noise_bound,
+            plane_offset_noise_bound: noise_bound,
+            line_direction_noise_bound: noise_bound,
+            line_offset_noise_bound: noise_bound,
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    pub fn set_point_noise_bound(&mut self, noise_bound: f64) {
+        self.point_noise_bound = noise_bound;
+    }
+
+    pub fn set_plane_direction_noise_bound(&mut self, noise_bound: f64) {
+        self.plane_direction_noise_bound = noise_bound;
+    }
+
+    pub fn set_plane_offset_noise_bound(&mut self, noise_bound: f64) {
+        self.plane_offset_noise_bound = noise_bound;
+    }
+
+    pub fn set_line_direction_noise_bound(&mut self, noise_bound: f64) {
+        self.line_direction_noise_bound = noise_bound;
+    }
+
+    pub fn set_line_offset_noise_bound(&mut self, noise_bound: f64) {
+        self.line_offset_noise_bound = noise_bound;
+    }
+
+    /// Computes the normalized matrix $N$ of a correspondence.
+    fn normalized_residual_mat(&self, correspondence: &Correspondence) -> Array2<f64> {
+        let mut mat_n = correspondence.residual_mat();
+
+        match correspondence {
+            Correspondence::Point(..) => mat_n.mapv_inplace(|x| x / self.point_noise_bound),
+            Correspondence::Plane(..) => reg_utils::normalize_paired_mat(
+                &mut mat_n,
+                self.plane_direction_noise_bound,
+                self.plane_offset_noise_bound,
+            ),
+            Correspondence::Line(..) => reg_utils::normalize_paired_mat(
+                &mut mat_n,
+                self.line_direction_noise_bound,
+                self.line_offset_noise_bound,
+            ),
+        }
+
+        mat_n
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for HeterogeneousSolver {
+    type Input<'a> = &'a [Correspondence];
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SE3.project(mat)
+    }
+
+    fn check_input(&self, correspondences: &Self::Input<'_>) {
+        assert!(
+            !correspondences.is_empty(),
+            "Input correspondences must not be empty"
+        );
+
+        for correspondence in correspondences.iter() {
+            let (a, b, len) = match correspondence {
+                Correspondence::Point(p, q) => (p, q, 3),
+                Correspondence::Plane(plane1, plane2) => (plane1, plane2, 4),
+                Correspondence::Line(line1, line2) => (line1, line2, 6),
+            };
+            assert!(
+                a.len() == len && b.len() == len,
+                "Input correspondences must have {} entries",
+                len
+            );
+        }
+    }
+
+    fn compute_terms(&self, correspondences: &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(correspondences.len());
+
+        for correspondence in correspondences.iter() {
+            let mat_n = self.normalized_residual_mat(correspondence);
+
+            terms.push(Fractional::new(R2Sym::new(mat_n.t().dot(&mat_n)), self.c()));
+        }
+
+        terms
+    }
+
+    fn compute_initial_guess(&self, correspondences: &Self::Input<'_>) -> Array2<f64> {
+        reg_utils::compute_least_squares_initial_guess(&self.compute_terms(correspondences))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for HeterogeneousSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for HeterogeneousSolver {}
+
+impl Certifiable<R2Sym> for HeterogeneousSolver {
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        rot_utils::so3_constraints(reg_utils::DIM, 0)
+    }
+}
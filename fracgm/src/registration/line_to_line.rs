@@ -0,0 +1,29 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::ArrayView1;
+
+use crate::registration::heterogeneous::Correspondence;
+use crate::registration::paired::{PairKind, PairedSolver};
+
+/// Lines, each of which is a row $[u; m]$ of Plücker coordinates with unit
+/// direction $u$ and moment $m = p \times u$ for any point $p$ on the line.
+pub struct Lines;
+
+impl PairKind for Lines {
+    const LEN: usize = 6;
+
+    fn correspondence(a: ArrayView1<f64>, b: ArrayView1<f64>) -> Correspondence {
+        Correspondence::Line(a.to_owned(), b.to_owned())
+    }
+}
+
+/// FracGM-based line-to-line registration solver with linear (naive)
+/// relaxation.
+///
+/// The direction residual $R u_1 - u_2$ is normalized by the direction noise
+/// bound, and the moment residual $R m_1 - [u_2]_\times t - m_2$ by the offset
+/// noise bound. At least two non-parallel lines are required to determine the
+/// transformation.
+pub type LineToLineSolver = PairedSolver<Lines>;
@@ -0,0 +1,142 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::marker::PhantomData;
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::certificate::Certifiable;
+use crate::manifold::{self, Manifold};
+use crate::registration::heterogeneous::Correspondence;
+use crate::registration::utils as reg_utils;
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// A kind of primitives whose rows are registered pairwise by
+/// [`PairedSolver`], e.g., planes or lines.
+pub trait PairKind {
+    /// The number of entries of a primitive, i.e., the number of columns of
+    /// the input.
+    const LEN: usize;
+
+    /// Get the correspondence of a pair of primitives, whose residual starts
+    /// with the three rows of the unit directions (normals of planes or
+    /// directions of lines).
+    fn correspondence(a: ArrayView1<f64>, b: ArrayView1<f64>) -> Correspondence;
+}
+
+/// FracGM-based registration solver of paired primitives with linear (naive)
+/// relaxation.
+///
+/// The residual of each pair is given by [`Correspondence::residual_mat`] in
+/// the lifted variable $[\mathrm{vec}(R); t; 1]$. Its first three rows compare
+/// unit directions and are normalized by `direction_noise_bound`, while the
+/// remaining rows are in the units of the scene and are normalized by
+/// `offset_noise_bound`.
+pub struct PairedSolver<K: PairKind> {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) of the direction residuals (default: 0.1).
+    pub direction_noise_bound: f64,
+    /// The noise bound (sigma) of the offset residuals (default: 0.1).
+    pub offset_noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    kind: PhantomData<K>,
+}
+
+impl<K: PairKind> PairedSolver<K> {
+    /// Create a solver whose direction and offset noise bounds are both
+    /// `noise_bound`. Use the setters to assign them individually.
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        let noise_bound = noise_bound.unwrap_or(0.1);
+
+        Self {
+            max_iteration,
+            tol,
+            direction_noise_bound: noise_bound,
+            offset_noise_bound: noise_bound,
+            c: c.unwrap_or(1.0),
+            kind: PhantomData,
+        }
+    }
+
+    pub fn set_direction_noise_bound(&mut self, noise_bound: f64) {
+        self.direction_noise_bound = noise_bound;
+    }
+
+    pub fn set_offset_noise_bound(&mut self, noise_bound: f64) {
+        self.offset_noise_bound = noise_bound;
+    }
+}
+
+impl<K: PairKind> FractionalProgrammingMaterials<R2Sym> for PairedSolver<K> {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_mat_to_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_vec_to_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SE3.project(mat)
+    }
+
+    fn check_input(&self, (prims1, prims2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(prims1, prims2, K::LEN);
+    }
+
+    fn compute_terms(&self, (prims1, prims2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(prims1.nrows());
+
+        for (a, b) in prims1.rows().into_iter().zip(prims2.rows()) {
+            let mut mat_n = K::correspondence(a, b).residual_mat();
+            reg_utils::normalize_paired_mat(
+                &mut mat_n,
+                self.direction_noise_bound,
+                self.offset_noise_bound,
+            );
+
+            terms.push(Fractional::new(R2Sym::new(mat_n.t().dot(&mat_n)), self.c()));
+        }
+
+        terms
+    }
+
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Array2<f64> {
+        reg_utils::compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl<K: PairKind> GemanMcclureSolver<R2Sym> for PairedSolver<K> {}
+impl<K: PairKind> GemanMcclureSolverDiagnostic<R2Sym> for PairedSolver<K> {}
+
+impl<K: PairKind> Certifiable<R2Sym> for PairedSolver<K> {
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        rot_utils::so3_constraints(reg_utils::DIM, 0)
+    }
+}
@@ -0,0 +1,29 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::ArrayView1;
+
+use crate::registration::heterogeneous::Correspondence;
+use crate::registration::paired::{PairKind, PairedSolver};
+
+/// Planes, each of which is a row $[n; d]$ of the set
+/// $\{x : n^\top x + d = 0\}$ with unit normal $n$.
+pub struct Planes;
+
+impl PairKind for Planes {
+    const LEN: usize = 4;
+
+    fn correspondence(a: ArrayView1<f64>, b: ArrayView1<f64>) -> Correspondence {
+        Correspondence::Plane(a.to_owned(), b.to_owned())
+    }
+}
+
+/// FracGM-based plane-to-plane registration solver with linear (naive)
+/// relaxation.
+///
+/// The normal residual $R n_1 - n_2$ is normalized by the direction noise
+/// bound, and the offset residual $n_2^\top t + d_2 - d_1$ by the offset noise
+/// bound. At least three planes with linearly independent normals are required
+/// to determine the translation.
+pub type PlaneToPlaneSolver = PairedSolver<Planes>;
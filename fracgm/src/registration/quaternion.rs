@@ -0,0 +1,89 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{array, Array2};
+
+use crate::manifold::{self, Manifold};
+use crate::registration::utils as reg_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based registration solver on the lifted quaternion variable.
+///
+/// The lifted variable stacks the upper triangular entries of $q q^\top$, the
+/// translation and the homogeneous coordinate. See
+/// [`crate::rotation::QuaternionSolver`] for details of the quaternion
+/// parameterization.
+pub struct QuaternionSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl QuaternionSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for QuaternionSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        reg_utils::QUAT_DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
+        reg_utils::se3_mat_to_quat_vec(mat)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
+        reg_utils::quat_vec_to_se3_mat(vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SE3.project(mat)
+    }
+
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, 3);
+    }
+
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        reg_utils::compute_quat_terms(pc1, pc2, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, (pc1, pc2): &Self::Input<'_>) -> Array2<f64> {
+        reg_utils::compute_initial_guess(pc1, pc2)
+    }
+
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        utils::solve_constrained(mat, &reg_utils::quat_constraint_mat(), &array![1.0, 1.0])
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for QuaternionSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for QuaternionSolver {}
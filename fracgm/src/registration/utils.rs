@@ -2,10 +2,14 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+use ndarray::linalg::kron;
 use ndarray::prelude::*;
 use ndarray::Array2;
 
+use crate::manifold::{self, Manifold};
 use crate::rotation::utils as rot_utils;
+use crate::solver::{Fractional, R2Sym};
+use crate::utils;
 
 pub const DIM: usize = 13;
 
@@ -29,3 +33,430 @@ pub fn se3_vec_to_mat(vec: &Array2<f64>) -> Array2<f64> {
 
     mat
 }
+
+pub fn compute_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    for i in 0..pc1.dim().0 {
+        let mat_n = point_to_point_mat(&pc1.row(i), &pc2.row(i));
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Computes the $3 \times 13$ matrix $N$ such that $N x = R p + t - q$, where
+/// $x = [\mathrm{vec}(R); t; 1]$.
+pub fn point_to_point_mat(p: &ArrayView1<f64>, q: &ArrayView1<f64>) -> Array2<f64> {
+    let id3 = Array2::eye(3);
+    let mut mat_n = Array2::zeros((3, DIM));
+
+    mat_n
+        .slice_mut(s![.., 0..9])
+        .assign(&kron(&p.into_shape((1, 3)).unwrap(), &id3));
+    mat_n.slice_mut(s![.., 9..12]).assign(&id3);
+    mat_n.slice_mut(s![.., 12]).assign(&q.mapv(|x| -x));
+
+    mat_n
+}
+
+/// Computes the Geman-McClure terms of the point-to-plane residuals $n^\top
+/// (R p + t - q)$, where $n$ is the unit normal of the target at $q$.
+pub fn compute_point_to_plane_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    normals: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    for i in 0..pc1.dim().0 {
+        let normal = normals.row(i).into_shape((1, 3)).unwrap();
+        let mat_n = normal.dot(&point_to_point_mat(&pc1.row(i), &pc2.row(i)));
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Computes the $4 \times 13$ matrix $N$ of a plane-to-plane correspondence,
+/// where a plane $[n; d]$ is the set $\{x : n^\top x + d = 0\}$. Since the
+/// plane $[n_1; d_1]$ is mapped to $[R n_1; d_1 - n_2^\top t]$, the residual
+/// $N x$ stacks $R n_1 - n_2$ and $n_2^\top t + d_2 - d_1$.
+pub fn plane_to_plane_mat(plane1: &ArrayView1<f64>, plane2: &ArrayView1<f64>) -> Array2<f64> {
+    let (n1, n2) = (plane1.slice(s![0..3]), plane2.slice(s![0..3]));
+    let mut mat_n = Array2::zeros((4, DIM));
+
+    mat_n
+        .slice_mut(s![0..3, ..])
+        .assign(&point_to_point_mat(&n1, &n2));
+    mat_n.slice_mut(s![0..3, 9..12]).fill(0.0);
+    mat_n.slice_mut(s![3, 9..12]).assign(&n2);
+    mat_n[[3, 12]] = plane2[3] - plane1[3];
+
+    mat_n
+}
+
+/// Computes the $6 \times 13$ matrix $N$ of a line-to-line correspondence,
+/// where a line $[u; m]$ is given by its Plücker coordinates, i.e., the unit
+/// direction $u$ and the moment $m = p \times u$ of any point $p$ on the line.
+/// Since the line $[u_1; m_1]$ is mapped to $[R u_1; R m_1 + t \times R u_1]$,
+/// the residual $N x$ stacks $R u_1 - u_2$ and $R m_1 - [u_2]_\times t - m_2$.
+pub fn line_to_line_mat(line1: &ArrayView1<f64>, line2: &ArrayView1<f64>) -> Array2<f64> {
+    let (u1, u2) = (line1.slice(s![0..3]), line2.slice(s![0..3]));
+    let (m1, m2) = (line1.slice(s![3..6]), line2.slice(s![3..6]));
+    let skew = array![
+        [0.0, -u2[2], u2[1]],
+        [u2[2], 0.0, -u2[0]],
+        [-u2[1], u2[0], 0.0]
+    ];
+    let mut mat_n = Array2::zeros((6, DIM));
+
+    mat_n
+        .slice_mut(s![0..3, ..])
+        .assign(&point_to_point_mat(&u1, &u2));
+    mat_n.slice_mut(s![0..3, 9..12]).fill(0.0);
+    mat_n
+        .slice_mut(s![3..6, ..])
+        .assign(&point_to_point_mat(&m1, &m2));
+    mat_n.slice_mut(s![3..6, 9..12]).assign(&skew.mapv(|x| -x));
+
+    mat_n
+}
+
+/// Normalizes the matrix $N$ of a plane-to-plane or line-to-line
+/// correspondence, whose first three rows compare unit directions and whose
+/// remaining rows are in the units of the scene.
+///
+/// # Arguments
+///
+/// - `mat_n` - The matrix $N$, see [`plane_to_plane_mat`] and
+///   [`line_to_line_mat`].
+/// - `direction_noise_bound` - The noise bound of the direction residuals.
+/// - `offset_noise_bound` - The noise bound of the offset residuals.
+pub fn normalize_paired_mat(
+    mat_n: &mut Array2<f64>,
+    direction_noise_bound: f64,
+    offset_noise_bound: f64,
+) {
+    mat_n
+        .slice_mut(s![0..3, ..])
+        .mapv_inplace(|x| x / direction_noise_bound);
+    mat_n
+        .slice_mut(s![3.., ..])
+        .mapv_inplace(|x| x / offset_noise_bound);
+}
+
+/// Computes the least squares initial guess of the terms, i.e., the projected
+/// minimizer of $\sum_i x^\top H_i x$ subject to $x_{13} = 1$. It serves
+/// correspondences without centroids, e.g., planes and lines.
+pub fn compute_least_squares_initial_guess(terms: &[Fractional<R2Sym>]) -> Array2<f64> {
+    let mut mat = Array2::<f64>::zeros((DIM, DIM));
+    for term in terms.iter() {
+        mat += term.h_mat();
+    }
+
+    manifold::SE3.project(&se3_vec_to_mat(&utils::solve_homogeneous(&mat)))
+}
+
+pub fn compute_initial_guess(pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    let (pc1, mean1) = &utils::get_zero_mean_point_cloud(pc1);
+    let (pc2, mean2) = &utils::get_zero_mean_point_cloud(pc2);
+
+    let mut mat = Array2::eye(4);
+
+    mat.slice_mut(s![0..3, 0..3])
+        .assign(&manifold::SO3.project(&pc2.t().dot(pc1)));
+    mat.slice_mut(s![0..3, 3]).assign(&(mean2 - mean1));
+
+    mat
+}
+
+/// The dimension of the lifted variable $[z; t; 1]$ of the quaternion-based
+/// registration solver, where $z$ is the lifted quaternion variable.
+pub const QUAT_DIM: usize = rot_utils::QUAT_DIM + 4;
+
+pub fn se3_mat_to_quat_vec(mat: &Array2<f64>) -> Array2<f64> {
+    let mut vec = Array2::<f64>::ones((QUAT_DIM, 1));
+    vec.slice_mut(s![0..rot_utils::QUAT_DIM, ..])
+        .assign(&rot_utils::rot_mat_to_quat_vec(
+            &mat.slice(s![0..3, 0..3]).to_owned(),
+        ));
+    vec.slice_mut(s![rot_utils::QUAT_DIM..QUAT_DIM - 1, 0])
+        .assign(&mat.slice(s![0..3, 3]));
+
+    vec
+}
+
+pub fn quat_vec_to_se3_mat(vec: &Array2<f64>) -> Array2<f64> {
+    let mut mat = Array2::<f64>::eye(4);
+    mat.slice_mut(s![0..3, 0..3])
+        .assign(&rot_utils::quat_vec_to_rot_mat(vec));
+    mat.slice_mut(s![0..3, 3])
+        .assign(&vec.slice(s![rot_utils::QUAT_DIM..QUAT_DIM - 1, 0]));
+
+    mat
+}
+
+/// Computes the matrix $W$ of the linear constraints $W^\top x = 1$ on the
+/// lifted variable, i.e., $\|q\|^2 = 1$ and the homogeneous coordinate equals
+/// 1.
+pub fn quat_constraint_mat() -> Array2<f64> {
+    let mut mat = Array2::<f64>::zeros((QUAT_DIM, 2));
+    mat.slice_mut(s![0..rot_utils::QUAT_DIM, 0])
+        .assign(&rot_utils::quat_trace_vec());
+    mat[[QUAT_DIM - 1, 1]] = 1.0;
+
+    mat
+}
+
+pub fn compute_quat_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    let id3 = Array2::eye(3);
+    let lift = rot_utils::quat_lift_mat();
+    for i in 0..pc1.dim().0 {
+        let mut mat_n = Array2::zeros((3, QUAT_DIM));
+
+        mat_n
+            .slice_mut(s![.., 0..rot_utils::QUAT_DIM])
+            .assign(&kron(&pc1.row(i).into_shape((1, 3)).unwrap(), &id3).dot(&lift));
+        mat_n
+            .slice_mut(s![.., rot_utils::QUAT_DIM..QUAT_DIM - 1])
+            .assign(&id3);
+        mat_n
+            .slice_mut(s![.., QUAT_DIM - 1])
+            .assign(&pc2.row(i).mapv(|x| -x));
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// The dimension of the lifted variable $[\cos\theta; \sin\theta; t; 1]$ of
+/// the planar registration solver.
+pub const SE2_DIM: usize = 5;
+
+pub fn se2_mat_to_vec(mat: &Array2<f64>) -> Array2<f64> {
+    let mut vec = Array2::<f64>::ones((SE2_DIM, 1));
+    vec.slice_mut(s![0..2, ..]).assign(
+        &rot_utils::so2_mat_to_vec(&mat.slice(s![0..2, 0..2]).to_owned()).slice(s![0..2, ..]),
+    );
+    vec.slice_mut(s![2..4, 0]).assign(&mat.slice(s![0..2, 2]));
+
+    vec
+}
+
+pub fn se2_vec_to_mat(vec: &Array2<f64>) -> Array2<f64> {
+    let mut mat = Array2::<f64>::eye(3);
+    mat.slice_mut(s![0..2, 0..2])
+        .assign(&rot_utils::so2_vec_to_mat(vec));
+    mat.slice_mut(s![0..2, 2]).assign(&vec.slice(s![2..4, 0]));
+
+    mat
+}
+
+pub fn compute_se2_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    let id2 = Array2::eye(2);
+    for i in 0..pc1.dim().0 {
+        let mut mat_n = Array2::zeros((2, SE2_DIM));
+
+        mat_n
+            .slice_mut(s![.., 0..2])
+            .assign(&rot_utils::so2_point_mat(&pc1.row(i)));
+        mat_n.slice_mut(s![.., 2..4]).assign(&id2);
+        mat_n.slice_mut(s![.., 4]).assign(&pc2.row(i).mapv(|x| -x));
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+pub fn compute_se2_initial_guess(pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    let (_, mean1) = &utils::get_zero_mean_point_cloud(pc1);
+    let (_, mean2) = &utils::get_zero_mean_point_cloud(pc2);
+
+    let rot = rot_utils::compute_so2_initial_guess(pc1, pc2);
+
+    let mut mat = Array2::eye(3);
+
+    mat.slice_mut(s![0..2, 0..2]).assign(&rot);
+    mat.slice_mut(s![0..2, 2])
+        .assign(&(mean2 - &rot.dot(mean1)));
+
+    mat
+}
+
+/// The dimension of the lifted variable $[\cos\theta; \sin\theta; t; 1]$ of
+/// the gravity-aligned (4-DoF) registration solver.
+pub const YAW_DIM: usize = 6;
+
+/// Computes the matrices $(g g^\top, I - g g^\top, [g]_\times)$ such that a
+/// rotation about the unit axis $g$ is $R = g g^\top + \cos\theta (I - g
+/// g^\top) + \sin\theta [g]_\times$.
+pub fn yaw_basis(gravity: &Array1<f64>) -> (Array2<f64>, Array2<f64>, Array2<f64>) {
+    let g = gravity.view().into_shape((3, 1)).unwrap();
+    let ggt = g.dot(&g.t());
+    let skew = array![
+        [0.0, -gravity[2], gravity[1]],
+        [gravity[2], 0.0, -gravity[0]],
+        [-gravity[1], gravity[0], 0.0]
+    ];
+
+    (ggt.clone(), Array2::<f64>::eye(3) - ggt, skew)
+}
+
+/// Computes the $13 \times 6$ matrix $T$ that maps the lifted variable of the
+/// gravity-aligned solver to the lifted variable $[\mathrm{vec}(R); t; 1]$ of
+/// the SE(3) solvers, so that the terms are $T^\top M T$.
+pub fn yaw_lift_mat(gravity: &Array1<f64>) -> Array2<f64> {
+    let (ggt, proj, skew) = yaw_basis(gravity);
+    let vec = |mat: &Array2<f64>| rot_utils::rot_mat_to_vec(mat).slice_move(s![0..9, 0]);
+
+    let mut mat = Array2::<f64>::zeros((DIM, YAW_DIM));
+    mat.slice_mut(s![0..9, 0]).assign(&vec(&proj));
+    mat.slice_mut(s![0..9, 1]).assign(&vec(&skew));
+    mat.slice_mut(s![9..12, 2..5])
+        .assign(&Array2::<f64>::eye(3));
+    mat.slice_mut(s![0..9, 5]).assign(&vec(&ggt));
+    mat[[12, 5]] = 1.0;
+
+    mat
+}
+
+pub fn yaw_mat_to_vec(mat: &Array2<f64>, gravity: &Array1<f64>) -> Array2<f64> {
+    let (_, proj, skew) = yaw_basis(gravity);
+    let rot = mat.slice(s![0..3, 0..3]);
+
+    let mut vec = Array2::<f64>::ones((YAW_DIM, 1));
+    vec[[0, 0]] = 0.5 * (&proj * &rot).sum();
+    vec[[1, 0]] = 0.5 * (&skew * &rot).sum();
+    vec.slice_mut(s![2..5, 0]).assign(&mat.slice(s![0..3, 3]));
+
+    vec
+}
+
+pub fn yaw_vec_to_mat(vec: &Array2<f64>, gravity: &Array1<f64>) -> Array2<f64> {
+    let (ggt, proj, skew) = yaw_basis(gravity);
+
+    let mut mat = Array2::<f64>::eye(4);
+    mat.slice_mut(s![0..3, 0..3])
+        .assign(&(ggt + vec[[0, 0]] * proj + vec[[1, 0]] * skew));
+    mat.slice_mut(s![0..3, 3]).assign(&vec.slice(s![2..5, 0]));
+
+    mat
+}
+
+/// Projects a 4x4 matrix onto the rigid transformations whose rotation is
+/// about the axis $g$.
+pub fn yaw_project(mat: &Array2<f64>, gravity: &Array1<f64>) -> Array2<f64> {
+    let mut vec = yaw_mat_to_vec(mat, gravity);
+    let norm = vec[[0, 0]].hypot(vec[[1, 0]]);
+    if norm > 0.0 {
+        vec[[0, 0]] /= norm;
+        vec[[1, 0]] /= norm;
+    } else {
+        vec[[0, 0]] = 1.0;
+    }
+
+    yaw_vec_to_mat(&vec, gravity)
+}
+
+/// Computes the Geman-McClure terms of the gravity-aligned solver by
+/// restricting the terms of [`compute_terms`] to rotations about $g$.
+pub fn compute_yaw_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    gravity: &Array1<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let lift = yaw_lift_mat(gravity);
+
+    compute_terms(pc1, pc2, noise_bound, c)
+        .iter()
+        .map(|term| {
+            let mat_m = lift.t().dot(term.h_mat()).dot(&lift);
+            Fractional::new(R2Sym::new(mat_m), c)
+        })
+        .collect()
+}
+
+/// Computes the closed-form least squares yaw and translation.
+pub fn compute_yaw_initial_guess(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    gravity: &Array1<f64>,
+) -> Array2<f64> {
+    let (c_pc1, mean1) = &utils::get_zero_mean_point_cloud(pc1);
+    let (c_pc2, mean2) = &utils::get_zero_mean_point_cloud(pc2);
+
+    // The yaw maximizing $\mathrm{tr}(R^\top C)$ is given by projecting the
+    // cross-covariance $C$.
+    let mut cov = Array2::<f64>::eye(4);
+    cov.slice_mut(s![0..3, 0..3]).assign(&c_pc2.t().dot(c_pc1));
+
+    let rot = yaw_project(&cov, gravity).slice_move(s![0..3, 0..3]);
+
+    let mut mat = Array2::<f64>::eye(4);
+    mat.slice_mut(s![0..3, 0..3]).assign(&rot);
+    mat.slice_mut(s![0..3, 3])
+        .assign(&(mean2 - &rot.dot(mean1)));
+
+    mat
+}
+
+/// Computes the closed-form least squares similarity transformation (Umeyama's
+/// method).
+pub fn compute_sim3_initial_guess(pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    let (c_pc1, mean1) = &utils::get_zero_mean_point_cloud(pc1);
+    let (c_pc2, mean2) = &utils::get_zero_mean_point_cloud(pc2);
+
+    let cov = c_pc2.t().dot(c_pc1);
+    let rot = manifold::SO3.project(&cov);
+
+    let var1 = c_pc1.iter().map(|x| x * x).sum::<f64>();
+    let scale = if var1 > 0.0 {
+        (&rot * &cov).sum().max(0.0) / var1
+    } else {
+        1.0
+    };
+
+    let mut mat = Array2::eye(4);
+
+    mat.slice_mut(s![0..3, 0..3]).assign(&(scale * &rot));
+    mat.slice_mut(s![0..3, 3])
+        .assign(&(mean2 - &(scale * rot.dot(mean1))));
+
+    mat
+}
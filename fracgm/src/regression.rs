@@ -0,0 +1,17 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solver of robust multivariate linear
+//! regression. Given the features $x_i$ and the targets $y_i$ of the samples,
+//! it estimates the coefficients $B$ and the intercept $b$ of the linear model
+//! $y = B^\top x + b$ from the residuals $y_i - B^\top x_i - b$, which are
+//! linear in the unknowns. The targets of a sample share a single
+//! Geman-McClure weight, so that a sample is either an inlier or an outlier
+//! as a whole.
+
+pub mod linear;
+pub mod utils;
+
+pub use linear::LinearSolver;
+pub use utils::{LinearModel, RegressionFit};
@@ -0,0 +1,172 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::regression::utils::{self as reg_utils, LinearModel, RegressionFit};
+use crate::solver::{Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, R2Sym};
+use crate::utils;
+
+/// FracGM-based robust linear regression solver.
+///
+/// The lifted variable is $[\mathrm{vec}(B); b; 1]$, and the noise bound is
+/// in the units of the targets, i.e., the expected norm of the residual of an
+/// inlier. Single-output regression uses targets with shape `(n, 1)`.
+///
+/// The dimension of the lifted variable depends on the numbers of features
+/// and targets, which are given by the columns of the samples. Thus
+/// [`LinearSolver::fit`] is the only entry point, and the FracGM materials are
+/// built for each fit.
+pub struct LinearSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    /// Whether the model has an intercept (default: true).
+    pub intercept: bool,
+}
+
+impl LinearSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+            intercept: true,
+        }
+    }
+
+    pub fn set_intercept(&mut self, intercept: bool) {
+        self.intercept = intercept;
+    }
+
+    /// Fit a linear model to the samples, and report the weight of each
+    /// sample and whether it is an inlier.
+    ///
+    /// # Arguments
+    ///
+    /// - `x` - The features with shape `(n, n_features)`.
+    /// - `y` - The targets with shape `(n, n_outputs)`.
+    ///
+    /// # Returns
+    ///
+    /// The fitted model with the weights and the inlier flags.
+    pub fn fit(&self, x: &Array2<f64>, y: &Array2<f64>) -> RegressionFit {
+        let regression = Regression {
+            solver: self,
+            n_features: x.ncols(),
+            n_outputs: y.ncols(),
+        };
+        let model = GemanMcclureSolver::solve(&regression, (x, y));
+
+        let mut terms = regression.compute_terms(&(x, y));
+        regression.update_terms_cache(&mut terms, &regression.mat_to_vec(&model));
+
+        RegressionFit {
+            model,
+            weights: utils::compute_weights(&terms, self.c),
+            inliers: utils::compute_inliers(&terms, self.c),
+        }
+    }
+}
+
+/// The FracGM materials of a regression with the given numbers of features
+/// and targets.
+struct Regression<'a> {
+    solver: &'a LinearSolver,
+    n_features: usize,
+    n_outputs: usize,
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for Regression<'_> {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = LinearModel;
+
+    fn dim(&self) -> usize {
+        let n_intercepts = if self.solver.intercept {
+            self.n_outputs
+        } else {
+            0
+        };
+
+        self.n_features * self.n_outputs + n_intercepts + 1
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.solver.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.solver.tol
+    }
+    fn c(&self) -> f64 {
+        self.solver.c
+    }
+
+    fn mat_to_vec(&self, model: &LinearModel) -> Array2<f64> {
+        let pq = self.n_features * self.n_outputs;
+
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        for (k, x) in model.coefficients.t().iter().enumerate() {
+            vec[[k, 0]] = *x;
+        }
+        if self.solver.intercept {
+            vec.slice_mut(s![pq..pq + self.n_outputs, 0])
+                .assign(&model.intercept);
+        }
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> LinearModel {
+        let p = self.n_features;
+        let q = self.n_outputs;
+
+        let mut coefficients = Array2::<f64>::zeros((p, q));
+        for k in 0..q {
+            coefficients
+                .column_mut(k)
+                .assign(&vec.slice(s![k * p..(k + 1) * p, 0]));
+        }
+        let intercept = match self.solver.intercept {
+            true => vec.slice(s![p * q..p * q + q, 0]).to_owned(),
+            false => Array1::<f64>::zeros(q),
+        };
+
+        LinearModel {
+            coefficients,
+            intercept,
+        }
+    }
+    fn project(&self, model: &LinearModel) -> LinearModel {
+        model.clone()
+    }
+
+    fn check_input(&self, (x, y): &Self::Input<'_>) {
+        let min_samples = self.n_features + if self.solver.intercept { 1 } else { 0 };
+
+        reg_utils::check_samples(x, y, self.n_features, self.n_outputs, min_samples);
+    }
+
+    fn compute_terms(&self, (x, y): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        reg_utils::compute_terms(
+            x,
+            y,
+            self.solver.intercept,
+            self.solver.noise_bound,
+            self.c(),
+        )
+    }
+
+    /// The ordinary least squares solution of all the samples.
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> LinearModel {
+        self.compute_least_squares_initial_guess(&self.compute_terms(input))
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for Regression<'_> {}
@@ -0,0 +1,143 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::solver::{Fractional, R2Sym};
+
+/// A linear model $y = B^\top x + b$.
+#[derive(Clone)]
+pub struct LinearModel {
+    /// The coefficients $B$ with shape `(n_features, n_outputs)`.
+    pub coefficients: Array2<f64>,
+    /// The intercept $b$ with shape `(n_outputs,)`, which is zero for models
+    /// without intercept.
+    pub intercept: Array1<f64>,
+}
+
+impl LinearModel {
+    /// Predict the targets of the samples.
+    ///
+    /// # Arguments
+    ///
+    /// - `x` - The features with shape `(n, n_features)`.
+    ///
+    /// # Returns
+    ///
+    /// The predicted targets with shape `(n, n_outputs)`.
+    pub fn predict(&self, x: &Array2<f64>) -> Array2<f64> {
+        assert!(
+            x.ncols() == self.coefficients.nrows(),
+            "Features must have {} columns",
+            self.coefficients.nrows()
+        );
+
+        x.dot(&self.coefficients) + &self.intercept
+    }
+
+    /// Get the norm of the residual $y_i - B^\top x_i - b$ of each sample.
+    pub fn residuals(&self, x: &Array2<f64>, y: &Array2<f64>) -> Vec<f64> {
+        (y - &self.predict(x))
+            .rows()
+            .into_iter()
+            .map(|r| r.dot(&r).sqrt())
+            .collect()
+    }
+}
+
+/// The result of a robust regression.
+#[derive(Clone)]
+pub struct RegressionFit {
+    /// The fitted linear model.
+    pub model: LinearModel,
+    /// The Geman-McClure weight of each sample, which is close to 1 for
+    /// inliers and vanishes for outliers.
+    pub weights: Vec<f64>,
+    /// Whether each sample is an inlier.
+    pub inliers: Vec<bool>,
+}
+
+/// Checks if the samples are valid for a regression with `n_features`
+/// features and `n_outputs` targets.
+///
+/// # Panics
+///
+/// Panics if the shapes of the features and the targets do not match, or the
+/// samples are fewer than `min_samples`.
+pub fn check_samples(
+    x: &Array2<f64>,
+    y: &Array2<f64>,
+    n_features: usize,
+    n_outputs: usize,
+    min_samples: usize,
+) {
+    assert!(
+        x.ncols() == n_features,
+        "Features must have {} columns",
+        n_features
+    );
+    assert!(
+        y.ncols() == n_outputs,
+        "Targets must have {} columns",
+        n_outputs
+    );
+    assert!(
+        x.nrows() == y.nrows(),
+        "Features and targets must have the same number of samples"
+    );
+    assert!(
+        x.nrows() >= min_samples,
+        "At least {} samples are required",
+        min_samples
+    );
+}
+
+/// Computes the Geman-McClure terms of the residuals $y_i - B^\top x_i - b$
+/// in the lifted variable $[\mathrm{vec}(B); b; 1]$, where $b$ is dropped if
+/// `intercept` is false.
+///
+/// # Arguments
+///
+/// - `x` - The features with shape `(n, n_features)`.
+/// - `y` - The targets with shape `(n, n_outputs)`.
+/// - `intercept` - Whether the model has an intercept.
+/// - `noise_bound` - The noise bound of the residuals.
+/// - `c` - The value of $c$ defined in Geman-McClure robust function.
+///
+/// # Returns
+///
+/// The Geman-McClure term of each sample.
+pub fn compute_terms(
+    x: &Array2<f64>,
+    y: &Array2<f64>,
+    intercept: bool,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let p = x.ncols();
+    let q = y.ncols();
+    let dim = p * q + if intercept { q } else { 0 } + 1;
+
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(x.nrows());
+
+    for (x_i, y_i) in x.rows().into_iter().zip(y.rows()) {
+        // $B^\top x_i = (I \otimes x_i^\top) \mathrm{vec}(B)$.
+        let mut mat_n = Array2::<f64>::zeros((q, dim));
+        for k in 0..q {
+            mat_n
+                .slice_mut(s![k, k * p..(k + 1) * p])
+                .assign(&x_i.mapv(|x| -x));
+            if intercept {
+                mat_n[[k, p * q + k]] = -1.0;
+            }
+            mat_n[[k, dim - 1]] = y_i[k];
+        }
+        mat_n.mapv_inplace(|x| x / noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_n.t().dot(&mat_n)), c));
+    }
+
+    terms
+}
@@ -3,6 +3,12 @@
 // license that can be found in the LICENSE file.
 
 pub mod linear;
+pub mod planar;
+pub mod quaternion;
+pub mod sdp;
 pub mod utils;
 
 pub use linear::LinearSolver;
+pub use planar::PlanarSolver;
+pub use quaternion::QuaternionSolver;
+pub use sdp::SdpSolver;
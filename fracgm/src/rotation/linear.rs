@@ -2,11 +2,10 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use ndarray::linalg::kron;
-use ndarray::prelude::*;
-use ndarray::Array2;
-use ndarray_linalg::*;
+use ndarray::{Array1, Array2};
 
+use crate::certificate::Certifiable;
+use crate::manifold::{self, Manifold};
 use crate::rotation::utils as rot_utils;
 use crate::solver::{
     Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
@@ -15,6 +14,10 @@ use crate::solver::{
 use crate::utils;
 
 /// FracGM-based rotation solver with linear (naive) relaxation.
+///
+/// It solves the robust orthogonal Procrustes problem of point clouds in $d$
+/// dimensions (default: 3), where the rotation is in SO(d), or in O(d) if
+/// reflections are allowed. The lifted variable is $[\mathrm{vec}(R); 1]$.
 pub struct LinearSolver {
     /// The maximum number of iterations allowed.
     pub max_iteration: usize,
@@ -24,6 +27,11 @@ pub struct LinearSolver {
     pub noise_bound: f64,
     /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
     pub c: f64,
+    /// The dimension of the point clouds (default: 3).
+    pub n_dims: usize,
+    /// Whether reflections are allowed, i.e., the rotation is in O(d) instead
+    /// of SO(d) (default: false).
+    pub reflection: bool,
 }
 
 impl LinearSolver {
@@ -33,13 +41,26 @@ impl LinearSolver {
             tol,
             noise_bound: noise_bound.unwrap_or(0.1),
             c: c.unwrap_or(1.0),
+            n_dims: 3,
+            reflection: false,
         }
     }
+
+    pub fn set_n_dims(&mut self, n_dims: usize) {
+        self.n_dims = n_dims;
+    }
+
+    pub fn set_reflection(&mut self, reflection: bool) {
+        self.reflection = reflection;
+    }
 }
 
 impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
     fn dim(&self) -> usize {
-        rot_utils::DIM
+        rot_utils::lifted_dim(self.n_dims)
     }
 
     fn max_iteration(&self) -> usize {
@@ -59,56 +80,37 @@ impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
         rot_utils::rot_vec_to_mat(rot_vec)
     }
     fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
-        utils::project(mat)
-    }
-
-    fn compute_terms(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Vec<Fractional<R2Sym>> {
-        let mut terms: Vec<Fractional<R2Sym>> = Vec::new();
-        terms.reserve(pc1.dim().0);
-
-        let id3 = Array2::eye(3);
-        for i in 0..pc1.dim().0 {
-            let mut mat_n = Array2::zeros((3, rot_utils::DIM));
-
-            mat_n
-                .slice_mut(s![.., 0..9])
-                .assign(&kron(&pc1.row(i).into_shape((1, 3)).unwrap(), &id3));
-            mat_n
-                .slice_mut(s![.., 9])
-                .assign(&pc2.row(i).mapv(|x| -1.0 * x));
-
-            let mat_m = mat_n.t().dot(&mat_n) / (self.noise_bound * self.noise_bound);
-
-            terms.push(Fractional::new(R2Sym::new(mat_m), self.c()));
+        match self.reflection {
+            true => manifold::Orthogonal::new(self.n_dims).project(mat),
+            false => manifold::SpecialOrthogonal::new(self.n_dims).project(mat),
         }
-
-        terms
     }
 
-    fn compute_initial_guess(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
-        let (pc1, _) = &utils::get_zero_mean_point_cloud(pc1);
-        let (pc2, _) = &utils::get_zero_mean_point_cloud(pc2);
-
-        utils::project(&pc2.t().dot(pc1))
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, self.n_dims);
     }
 
-    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
-        let e = array![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 1.0];
-
-        let lu_factor = mat.clone().factorize_into().unwrap();
-
-        let y = lu_factor
-            .solve_into(e)
-            .unwrap()
-            .into_shape((10, 1))
-            .unwrap();
-        let schur = y[[9, 0]];
-
-        let x = (1.0 / schur) * y;
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        rot_utils::compute_terms(pc1, pc2, self.noise_bound, self.c())
+    }
 
-        x
+    /// The least squares rotation, i.e., the cross-covariance of the point
+    /// clouds projected onto SO(d) or O(d).
+    fn compute_initial_guess(&self, (pc1, pc2): &Self::Input<'_>) -> Array2<f64> {
+        self.project(&rot_utils::cross_covariance(pc1, pc2))
     }
 }
 
 impl GemanMcclureSolver<R2Sym> for LinearSolver {}
 impl GemanMcclureSolverDiagnostic<R2Sym> for LinearSolver {}
+
+impl Certifiable<R2Sym> for LinearSolver {
+    /// The constraints of SO(3), or of O(d) otherwise. The latter is a
+    /// relaxation of SO(d) for $d \ne 3$, which still certifies rotations.
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        match self.n_dims == 3 && !self.reflection {
+            true => rot_utils::so3_constraints(self.dim(), 0),
+            false => rot_utils::orthogonal_constraints(self.n_dims, self.dim(), 0),
+        }
+    }
+}
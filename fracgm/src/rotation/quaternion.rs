@@ -0,0 +1,98 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::{array, Array2};
+
+use crate::manifold::{self, Manifold};
+use crate::rotation::utils as rot_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based rotation solver on the lifted quaternion variable.
+///
+/// The rotation is parameterized by a unit quaternion $q$ and the residuals
+/// are quadratic in $q$, so they are linear in the upper triangular entries
+/// of $q q^\top$. The unit norm of $q$ becomes the linear constraint
+/// $\mathrm{tr}(q q^\top) = 1$, which plays the role of the homogeneous
+/// coordinate of [`crate::rotation::LinearSolver`].
+///
+/// The solution is rounded with the leading eigenvector of $q q^\top$, and
+/// [`rot_utils::quat_rank_one_ratio`] of the iterates in the diagnostic
+/// information measures the tightness of the relaxation.
+pub struct QuaternionSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl QuaternionSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for QuaternionSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        rot_utils::QUAT_DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, rot: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_mat_to_quat_vec(rot)
+    }
+    fn vec_to_mat(&self, quat_vec: &Array2<f64>) -> Array2<f64> {
+        rot_utils::quat_vec_to_rot_mat(quat_vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SO3.project(mat)
+    }
+
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, 3);
+    }
+
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        rot_utils::compute_quat_terms(pc1, pc2, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, (pc1, pc2): &Self::Input<'_>) -> Array2<f64> {
+        rot_utils::compute_initial_guess(pc1, pc2)
+    }
+
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        let trace = rot_utils::quat_trace_vec()
+            .into_shape((rot_utils::QUAT_DIM, 1))
+            .unwrap();
+
+        utils::solve_constrained(mat, &trace, &array![1.0])
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for QuaternionSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for QuaternionSolver {}
@@ -0,0 +1,135 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::certificate::Certifiable;
+use crate::manifold::{self, Manifold};
+use crate::rotation::utils as rot_utils;
+use crate::sdp;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based rotation solver with semidefinite relaxation.
+///
+/// Unlike [`crate::rotation::LinearSolver`], the convex subproblem of each
+/// iteration keeps the (redundant) quadratic orthogonality and handedness
+/// constraints of SO(3), and is solved as a semidefinite program.
+///
+/// If the interior point method does not converge or the relaxation is not
+/// tight, i.e., no rank-one solution can be extracted, the subproblem falls
+/// back to the minimizer without the quadratic constraints, as in
+/// [`crate::rotation::LinearSolver`]. This is common with few correspondences
+/// or many outliers, and is counted by [`SdpSolver::n_fallbacks`].
+pub struct SdpSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+    /// The SDP solver for the convex subproblem.
+    pub sdp_solver: sdp::Solver,
+
+    constraints: Vec<Array2<f64>>,
+    rhs: Array1<f64>,
+    n_fallbacks: AtomicUsize,
+}
+
+impl SdpSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        let (constraints, rhs) = rot_utils::so3_constraints(rot_utils::DIM, 0);
+
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+            sdp_solver: sdp::Solver::default(),
+            constraints,
+            rhs,
+            n_fallbacks: AtomicUsize::new(0),
+        }
+    }
+
+    /// Get the number of subproblems solved by this solver that fell back to
+    /// the minimizer without the quadratic constraints, see
+    /// [`SdpSolver`]. Compare the numbers before and after a solve to tell
+    /// whether the iterates of its [`crate::solver::Diagnostic`] include such
+    /// fallbacks.
+    pub fn n_fallbacks(&self) -> usize {
+        self.n_fallbacks.load(Ordering::Relaxed)
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for SdpSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
+    fn dim(&self) -> usize {
+        rot_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, rot: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_mat_to_vec(rot)
+    }
+    fn vec_to_mat(&self, rot_vec: &Array2<f64>) -> Array2<f64> {
+        rot_utils::rot_vec_to_mat(rot_vec)
+    }
+    fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
+        manifold::SO3.project(mat)
+    }
+
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, 3);
+    }
+
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        rot_utils::compute_terms(pc1, pc2, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, (pc1, pc2): &Self::Input<'_>) -> Array2<f64> {
+        rot_utils::compute_initial_guess(pc1, pc2)
+    }
+
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        match self
+            .sdp_solver
+            .solve_rank_one(mat, &self.constraints, &self.rhs)
+        {
+            Some(vec) => vec,
+            None => {
+                self.n_fallbacks.fetch_add(1, Ordering::Relaxed);
+                utils::solve_homogeneous(mat)
+            }
+        }
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for SdpSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for SdpSolver {}
+
+impl Certifiable<R2Sym> for SdpSolver {
+    fn constraints(&self) -> (Vec<Array2<f64>>, Array1<f64>) {
+        (self.constraints.clone(), self.rhs.clone())
+    }
+}
@@ -2,27 +2,464 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
+use ndarray::linalg::kron;
 use ndarray::prelude::*;
 use ndarray::Array2;
+use ndarray_linalg::{EigValsh, Eigh, UPLO};
+
+use crate::manifold::{self, Manifold};
+use crate::solver::{Fractional, R2Sym};
+use crate::utils;
 
 pub const DIM: usize = 10;
 
+/// Gets the dimension $d^2 + 1$ of the lifted variable $[\mathrm{vec}(R); 1]$
+/// of a rotation in $d$ dimensions, e.g., [`DIM`] for $d = 3$.
+pub fn lifted_dim(n_dims: usize) -> usize {
+    n_dims * n_dims + 1
+}
+
+/// Converts a $d \times d$ rotation to the lifted variable
+/// $[\mathrm{vec}(R); 1]$.
 pub fn rot_mat_to_vec(rot: &Array2<f64>) -> Array2<f64> {
-    let mut rot_vec = Array2::<f64>::ones((DIM, 1));
+    let d = rot.nrows();
+
+    let mut rot_vec = Array2::<f64>::ones((lifted_dim(d), 1));
     rot_vec
-        .slice_mut(s![0..9, 0])
-        .assign(&rot.clone().t().into_shape(9).unwrap());
+        .slice_mut(s![0..d * d, 0])
+        .assign(&rot.t().iter().cloned().collect::<Array1<f64>>());
 
     rot_vec
 }
 
+/// Converts the lifted variable $[\mathrm{vec}(R); 1]$, with or without the
+/// homogeneous coordinate, back to the $d \times d$ rotation.
 pub fn rot_vec_to_mat(rot_vec: &Array2<f64>) -> Array2<f64> {
+    let d = (rot_vec.nrows() as f64).sqrt().floor() as usize;
+
     let mut rot = rot_vec
         .clone()
-        .slice_move(s![0..9, 0])
-        .into_shape((3, 3))
+        .slice_move(s![0..d * d, 0])
+        .into_shape((d, d))
         .unwrap();
     rot.swap_axes(0, 1);
 
     rot
 }
+
+/// Computes the Geman-McClure terms of the residuals $R p_i - q_i$ in the
+/// lifted variable $[\mathrm{vec}(R); 1]$, where the dimension $d$ is the
+/// number of columns of the point clouds.
+pub fn compute_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    let d = pc1.ncols();
+    let dim = lifted_dim(d);
+    let id = Array2::eye(d);
+    for i in 0..pc1.dim().0 {
+        let mut mat_n = Array2::zeros((d, dim));
+
+        mat_n
+            .slice_mut(s![.., 0..d * d])
+            .assign(&kron(&pc1.row(i).into_shape((1, d)).unwrap(), &id));
+        mat_n
+            .slice_mut(s![.., d * d])
+            .assign(&pc2.row(i).mapv(|x| -x));
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// Computes the cross-covariance $\sum_i (q_i - \bar{q}) (p_i - \bar{p})^\top$
+/// of the point clouds, whose projection onto SO(d) or O(d) is the least
+/// squares rotation (orthogonal Procrustes).
+pub fn cross_covariance(pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    let (pc1, _) = &utils::get_zero_mean_point_cloud(pc1);
+    let (pc2, _) = &utils::get_zero_mean_point_cloud(pc2);
+
+    pc2.t().dot(pc1)
+}
+
+/// Computes the least squares rotation in SO(d) of the point clouds.
+pub fn compute_initial_guess(pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    manifold::SpecialOrthogonal::new(pc1.ncols()).project(&cross_covariance(pc1, pc2))
+}
+
+/// The dimension of the lifted quaternion variable $z$, which stacks the
+/// upper triangular entries $q_i q_j$ ($i \le j$) of $q q^\top$ row by row. The
+/// quaternion $q = (w, x, y, z)$ is stored scalar-first.
+pub const QUAT_DIM: usize = 10;
+
+/// Gets the index of $q_i q_j$ in the lifted quaternion variable.
+pub fn quat_index(i: usize, j: usize) -> usize {
+    let (i, j) = if i <= j { (i, j) } else { (j, i) };
+    assert!(j < 4, "Quaternion index out of range");
+
+    i * (9 - i) / 2 + (j - i)
+}
+
+/// Computes the $9 \times 10$ matrix $L$ such that $\mathrm{vec}(R(q)) = L z$,
+/// where $z$ is the lifted quaternion variable.
+pub fn quat_lift_mat() -> Array2<f64> {
+    let idx = |row: usize, col: usize| 3 * col + row;
+
+    let mut mat = Array2::<f64>::zeros((9, QUAT_DIM));
+
+    // The diagonal entries, e.g., $R_{00} = w^2 + x^2 - y^2 - z^2$.
+    for k in 0..3 {
+        mat[[idx(k, k), quat_index(0, 0)]] = 1.0;
+        for i in 1..4 {
+            mat[[idx(k, k), quat_index(i, i)]] = if i == k + 1 { 1.0 } else { -1.0 };
+        }
+    }
+
+    // The off-diagonal entries, e.g., $R_{01} = 2 (xy - wz)$ and $R_{10} = 2
+    // (xy + wz)$.
+    for (a, b, k) in [(0, 1, 2), (1, 2, 0), (2, 0, 1)] {
+        mat[[idx(a, b), quat_index(a + 1, b + 1)]] = 2.0;
+        mat[[idx(a, b), quat_index(0, k + 1)]] = -2.0;
+        mat[[idx(b, a), quat_index(a + 1, b + 1)]] = 2.0;
+        mat[[idx(b, a), quat_index(0, k + 1)]] = 2.0;
+    }
+
+    mat
+}
+
+/// Computes the vector $e$ such that $e^\top z = \|q\|^2$, i.e., the trace of
+/// $q q^\top$.
+pub fn quat_trace_vec() -> Array1<f64> {
+    let mut vec = Array1::<f64>::zeros(QUAT_DIM);
+    for i in 0..4 {
+        vec[quat_index(i, i)] = 1.0;
+    }
+
+    vec
+}
+
+/// Converts a rotation matrix to a unit quaternion $(w, x, y, z)$ with
+/// Shepperd's method.
+pub fn rot_mat_to_quat(rot: &Array2<f64>) -> Array1<f64> {
+    let r = |i: usize, j: usize| rot[[i, j]];
+    let trace = r(0, 0) + r(1, 1) + r(2, 2);
+
+    let quat = if trace > 0.0 {
+        let s = 2.0 * (trace + 1.0).sqrt();
+        array![
+            0.25 * s,
+            (r(2, 1) - r(1, 2)) / s,
+            (r(0, 2) - r(2, 0)) / s,
+            (r(1, 0) - r(0, 1)) / s
+        ]
+    } else if r(0, 0) > r(1, 1) && r(0, 0) > r(2, 2) {
+        let s = 2.0 * (1.0 + r(0, 0) - r(1, 1) - r(2, 2)).sqrt();
+        array![
+            (r(2, 1) - r(1, 2)) / s,
+            0.25 * s,
+            (r(0, 1) + r(1, 0)) / s,
+            (r(0, 2) + r(2, 0)) / s
+        ]
+    } else if r(1, 1) > r(2, 2) {
+        let s = 2.0 * (1.0 + r(1, 1) - r(0, 0) - r(2, 2)).sqrt();
+        array![
+            (r(0, 2) - r(2, 0)) / s,
+            (r(0, 1) + r(1, 0)) / s,
+            0.25 * s,
+            (r(1, 2) + r(2, 1)) / s
+        ]
+    } else {
+        let s = 2.0 * (1.0 + r(2, 2) - r(0, 0) - r(1, 1)).sqrt();
+        array![
+            (r(1, 0) - r(0, 1)) / s,
+            (r(0, 2) + r(2, 0)) / s,
+            (r(1, 2) + r(2, 1)) / s,
+            0.25 * s
+        ]
+    };
+
+    &quat / quat.dot(&quat).sqrt()
+}
+
+/// Converts a quaternion $(w, x, y, z)$ to a rotation matrix. The quaternion
+/// is normalized implicitly.
+pub fn quat_to_rot_mat(quat: &Array1<f64>) -> Array2<f64> {
+    let rot_vec = quat_lift_mat().dot(&quat_lift(quat)) / quat.dot(quat);
+
+    let mut rot = rot_vec.into_shape((3, 3)).unwrap();
+    rot.swap_axes(0, 1);
+
+    rot
+}
+
+/// Lifts a quaternion $q$ to the upper triangular entries of $q q^\top$.
+pub fn quat_lift(quat: &Array1<f64>) -> Array1<f64> {
+    assert!(quat.len() == 4, "Quaternion must have 4 entries");
+
+    let mut vec = Array1::<f64>::zeros(QUAT_DIM);
+    for i in 0..4 {
+        for j in i..4 {
+            vec[quat_index(i, j)] = quat[i] * quat[j];
+        }
+    }
+
+    vec
+}
+
+pub fn rot_mat_to_quat_vec(rot: &Array2<f64>) -> Array2<f64> {
+    quat_lift(&rot_mat_to_quat(rot))
+        .into_shape((QUAT_DIM, 1))
+        .unwrap()
+}
+
+/// Converts a lifted quaternion variable to a rotation matrix. The quaternion
+/// is recovered from the leading eigenvector of $q q^\top$, so the result is
+/// always a rotation matrix even if the lifted variable is not of rank one.
+pub fn quat_vec_to_rot_mat(quat_vec: &Array2<f64>) -> Array2<f64> {
+    let (_, eigvecs) = quat_vec_to_quat_mat(quat_vec).eigh(UPLO::Lower).unwrap();
+
+    quat_to_rot_mat(&eigvecs.column(3).to_owned())
+}
+
+/// Converts a lifted quaternion variable to the symmetric matrix $q q^\top$.
+pub fn quat_vec_to_quat_mat(quat_vec: &Array2<f64>) -> Array2<f64> {
+    let mut mat = Array2::<f64>::zeros((4, 4));
+    for i in 0..4 {
+        for j in i..4 {
+            mat[[i, j]] = quat_vec[[quat_index(i, j), 0]];
+            mat[[j, i]] = quat_vec[[quat_index(i, j), 0]];
+        }
+    }
+
+    mat
+}
+
+/// Computes the ratio between the second largest and the largest eigenvalues
+/// (in magnitude) of $q q^\top$ recovered from a lifted quaternion variable.
+/// A ratio close to zero indicates that the relaxation is tight.
+pub fn quat_rank_one_ratio(quat_vec: &Array2<f64>) -> f64 {
+    let mut eigvals = quat_vec_to_quat_mat(quat_vec)
+        .eigvalsh(UPLO::Lower)
+        .unwrap()
+        .mapv(f64::abs)
+        .to_vec();
+    eigvals.sort_by(|a, b| b.partial_cmp(a).unwrap());
+
+    eigvals[1] / eigvals[0]
+}
+
+/// Computes the Geman-McClure terms on the lifted quaternion variable. The
+/// residual $R(q) p - p'$ is homogenized as $R(q) p - \|q\|^2 p'$, which is
+/// linear in $z$.
+pub fn compute_quat_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    let id3 = Array2::eye(3);
+    let lift = quat_lift_mat();
+    let trace = quat_trace_vec().into_shape((1, QUAT_DIM)).unwrap();
+    for i in 0..pc1.dim().0 {
+        let mat_n = kron(&pc1.row(i).into_shape((1, 3)).unwrap(), &id3).dot(&lift)
+            - pc2.row(i).into_shape((3, 1)).unwrap().dot(&trace);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+/// The dimension of the lifted variable $[\cos\theta; \sin\theta; 1]$ of the
+/// planar rotation solver.
+pub const SO2_DIM: usize = 3;
+
+pub fn so2_mat_to_vec(rot: &Array2<f64>) -> Array2<f64> {
+    array![
+        [0.5 * (rot[[0, 0]] + rot[[1, 1]])],
+        [0.5 * (rot[[1, 0]] - rot[[0, 1]])],
+        [1.0]
+    ]
+}
+
+pub fn so2_vec_to_mat(rot_vec: &Array2<f64>) -> Array2<f64> {
+    let (cos, sin) = (rot_vec[[0, 0]], rot_vec[[1, 0]]);
+
+    array![[cos, -sin], [sin, cos]]
+}
+
+/// Computes the $2 \times 2$ matrix $P$ such that $R p = P [\cos\theta;
+/// \sin\theta]$ for a planar point $p$.
+pub fn so2_point_mat(point: &ArrayView1<f64>) -> Array2<f64> {
+    array![[point[0], -point[1]], [point[1], point[0]]]
+}
+
+pub fn compute_so2_terms(
+    pc1: &Array2<f64>,
+    pc2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
+
+    for i in 0..pc1.dim().0 {
+        let mut mat_n = Array2::zeros((2, SO2_DIM));
+
+        mat_n
+            .slice_mut(s![.., 0..2])
+            .assign(&so2_point_mat(&pc1.row(i)));
+        mat_n.slice_mut(s![.., 2]).assign(&pc2.row(i).mapv(|x| -x));
+
+        let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
+
+pub fn compute_so2_initial_guess(pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    let (pc1, _) = &utils::get_zero_mean_point_cloud(pc1);
+    let (pc2, _) = &utils::get_zero_mean_point_cloud(pc2);
+
+    manifold::SO2.project(&pc2.t().dot(pc1))
+}
+
+/// Computes the quadratic constraints of SO(2) on a lifted variable $x$ of
+/// dimension `dim`, where $x$ contains $(\cos\theta, \sin\theta)$ starting
+/// from index `offset` and the homogeneous coordinate is the last entry of
+/// $x$.
+///
+/// # Returns
+///
+/// The constraint matrices $A_i$ and the right-hand side $b$.
+pub fn so2_constraints(dim: usize, offset: usize) -> (Vec<Array2<f64>>, Array1<f64>) {
+    assert!(offset + 2 < dim, "Lifted variable is too small");
+
+    let mut mat_h = Array2::<f64>::zeros((dim, dim));
+    mat_h[[dim - 1, dim - 1]] = 1.0;
+
+    let mut mat_r = Array2::<f64>::zeros((dim, dim));
+    mat_r[[offset, offset]] = 1.0;
+    mat_r[[offset + 1, offset + 1]] = 1.0;
+
+    (vec![mat_h, mat_r], array![1.0, 1.0])
+}
+
+/// Computes the quadratic constraints of SO(3) on a lifted variable $x$ of
+/// dimension `dim`, where $x$ contains $\mathrm{vec}(R)$ starting from index
+/// `offset` and the homogeneous coordinate is the last entry of $x$.
+///
+/// Each constraint is written as $x^\top A_i x = b_i$. The constraints
+/// consist of the homogeneous coordinate ($h^2 = 1$), the orthonormality of
+/// columns ($R^\top R = I$) and rows ($R R^\top = I$), and the right-hand rule
+/// of columns ($r_i \times r_j = r_k$). The redundant constraint on the norm
+/// of the last row is dropped so that the constraints are linearly
+/// independent.
+///
+/// # Returns
+///
+/// The constraint matrices $A_i$ and the right-hand side $b$.
+pub fn so3_constraints(dim: usize, offset: usize) -> (Vec<Array2<f64>>, Array1<f64>) {
+    let (mut mats, rhs) = orthogonal_constraints(3, dim, offset);
+    let mut rhs = rhs.to_vec();
+
+    let idx = |row: usize, col: usize| offset + 3 * col + row;
+    let h = dim - 1;
+
+    for (p, q, r) in [(0, 1, 2), (1, 2, 0), (2, 0, 1)] {
+        for k in 0..3 {
+            let k1 = (k + 1) % 3;
+            let k2 = (k + 2) % 3;
+
+            let mut mat = Array2::<f64>::zeros((dim, dim));
+            add_sym(&mut mat, idx(k1, p), idx(k2, q), 1.0);
+            add_sym(&mut mat, idx(k2, p), idx(k1, q), -1.0);
+            add_sym(&mut mat, idx(k, r), h, -1.0);
+            mats.push(mat);
+            rhs.push(0.0);
+        }
+    }
+
+    (mats, Array1::from_vec(rhs))
+}
+
+/// Computes the quadratic constraints of O(d) on a lifted variable $x$ of
+/// dimension `dim`, where $x$ contains $\mathrm{vec}(R)$ of a $d \times d$
+/// matrix starting from index `offset` and the homogeneous coordinate is the
+/// last entry of $x$.
+///
+/// The constraints consist of the homogeneous coordinate ($h^2 = 1$) and the
+/// orthonormality of columns ($R^\top R = I$) and rows ($R R^\top = I$),
+/// without the redundant constraint on the norm of the last row. They are
+/// also a relaxation of SO(d), whose determinant constraint is not quadratic
+/// for $d \ne 3$.
+///
+/// # Returns
+///
+/// The constraint matrices $A_i$ and the right-hand side $b$.
+pub fn orthogonal_constraints(
+    n_dims: usize,
+    dim: usize,
+    offset: usize,
+) -> (Vec<Array2<f64>>, Array1<f64>) {
+    let d = n_dims;
+    assert!(offset + d * d < dim, "Lifted variable is too small");
+
+    let idx = |row: usize, col: usize| offset + d * col + row;
+    let h = dim - 1;
+
+    let mut mats: Vec<Array2<f64>> = Vec::new();
+    let mut rhs: Vec<f64> = Vec::new();
+
+    let mut mat = Array2::<f64>::zeros((dim, dim));
+    add_sym(&mut mat, h, h, 1.0);
+    mats.push(mat);
+    rhs.push(1.0);
+
+    for a in 0..d {
+        for b in a..d {
+            let mut mat = Array2::<f64>::zeros((dim, dim));
+            for i in 0..d {
+                add_sym(&mut mat, idx(i, a), idx(i, b), 1.0);
+            }
+            mats.push(mat);
+            rhs.push(if a == b { 1.0 } else { 0.0 });
+        }
+    }
+
+    for a in 0..d {
+        for b in a..d {
+            if a == d - 1 && b == d - 1 {
+                continue;
+            }
+
+            let mut mat = Array2::<f64>::zeros((dim, dim));
+            for j in 0..d {
+                add_sym(&mut mat, idx(a, j), idx(b, j), 1.0);
+            }
+            mats.push(mat);
+            rhs.push(if a == b { 1.0 } else { 0.0 });
+        }
+    }
+
+    (mats, Array1::from_vec(rhs))
+}
+
+/// Adds the bilinear term $v x_i x_j$ to the quadratic form of a symmetric
+/// matrix.
+fn add_sym(mat: &mut Array2<f64>, i: usize, j: usize, v: f64) {
+    mat[[i, j]] += 0.5 * v;
+    mat[[j, i]] += 0.5 * v;
+}
@@ -0,0 +1,8 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+pub mod linear;
+pub mod utils;
+
+pub use linear::LinearSolver;
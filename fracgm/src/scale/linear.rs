@@ -0,0 +1,84 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::Array2;
+
+use crate::scale::utils as scale_utils;
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::utils;
+
+/// FracGM-based scale solver over the length ratios of translation invariant
+/// measurements (TIMs).
+///
+/// The input is a pair of TIMs (e.g., computed by
+/// [`crate::utils::compute_chain_translation_invariant_measurements`]), which
+/// are invariant to translation and whose lengths are invariant to rotation.
+pub struct LinearSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl LinearSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = f64;
+
+    fn dim(&self) -> usize {
+        scale_utils::DIM
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, scale: &f64) -> Array2<f64> {
+        scale_utils::scale_to_vec(scale)
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> f64 {
+        scale_utils::vec_to_scale(vec)
+    }
+    fn project(&self, scale: &f64) -> f64 {
+        scale.max(0.0)
+    }
+
+    fn check_input(&self, (tims1, tims2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(tims1, tims2, tims1.dim().1);
+    }
+
+    fn compute_terms(&self, (tims1, tims2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        scale_utils::compute_terms(tims1, tims2, self.noise_bound, self.c())
+    }
+
+    fn compute_initial_guess(&self, (tims1, tims2): &Self::Input<'_>) -> f64 {
+        scale_utils::compute_initial_guess(tims1, tims2)
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for LinearSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for LinearSolver {}
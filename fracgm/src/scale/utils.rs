@@ -0,0 +1,69 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::solver::{Fractional, R2Sym};
+
+pub const DIM: usize = 2;
+
+pub fn scale_to_vec(scale: &f64) -> Array2<f64> {
+    array![[*scale], [1.0]]
+}
+
+pub fn vec_to_scale(vec: &Array2<f64>) -> f64 {
+    vec[[0, 0]]
+}
+
+/// Computes the lengths of the translation invariant measurements.
+pub fn compute_tim_lengths(tims: &Array2<f64>) -> Array1<f64> {
+    tims.map_axis(Axis(1), |tim| tim.dot(&tim).sqrt())
+}
+
+/// Computes the Geman-McClure terms of the residuals $s \|a_k\| - \|b_k\|$,
+/// where $a_k$ and $b_k$ are the paired translation invariant measurements.
+/// Equivalently, the ratio $\|b_k\| / \|a_k\|$ is weighted by $\|a_k\|$.
+pub fn compute_terms(
+    tims1: &Array2<f64>,
+    tims2: &Array2<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let lengths1 = compute_tim_lengths(tims1);
+    let lengths2 = compute_tim_lengths(tims2);
+
+    lengths1
+        .iter()
+        .zip(lengths2.iter())
+        .map(|(l1, l2)| {
+            let mat_n = array![[*l1, -l2]];
+            let mat_m = mat_n.t().dot(&mat_n) / (noise_bound * noise_bound);
+
+            Fractional::new(R2Sym::new(mat_m), c)
+        })
+        .collect()
+}
+
+/// Computes the median of the length ratios $\|b_k\| / \|a_k\|$, which is a
+/// robust initial guess of the scale.
+pub fn compute_initial_guess(tims1: &Array2<f64>, tims2: &Array2<f64>) -> f64 {
+    let lengths1 = compute_tim_lengths(tims1);
+    let lengths2 = compute_tim_lengths(tims2);
+
+    let mut ratios: Vec<f64> = lengths1
+        .iter()
+        .zip(lengths2.iter())
+        .filter(|(l1, _)| **l1 > 0.0)
+        .map(|(l1, l2)| l2 / l1)
+        .collect();
+
+    if ratios.is_empty() {
+        return 1.0;
+    }
+
+    ratios.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+    ratios[ratios.len() / 2]
+}
@@ -0,0 +1,335 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains a small dense semidefinite programming (SDP) solver
+//! that is used by the tighter relaxations of FracGM.
+//!
+//! The solver handles problems in the standard primal form
+//!
+//! $$
+//! \min_X \langle C, X \rangle \quad \text{s.t.} \quad \langle A_i, X \rangle = b_i, \quad X \succeq 0,
+//! $$
+//!
+//! together with the dual problem
+//!
+//! $$
+//! \max_{y, Z} b^\top y \quad \text{s.t.} \quad C - \sum_i y_i A_i = Z, \quad Z \succeq 0,
+//! $$
+//!
+//! with an infeasible primal-dual interior point method (HKM search direction).
+//! It is meant for the small problems (dimension about 10 to 20) that arise
+//! from the lifted FracGM variables.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::*;
+
+/// The tolerance of the homogeneous coordinate of the unit leading
+/// eigenvector below which no rank-one vector is extracted, see
+/// [`Solution::rank_one_vec`].
+pub const HOMOGENEOUS_TOL: f64 = 1e-9;
+
+/// The solution of a semidefinite program.
+#[derive(Clone)]
+pub struct Solution {
+    /// The primal variable $X$.
+    pub x: Array2<f64>,
+    /// The dual variable $y$ (Lagrange multipliers of the equality
+    /// constraints).
+    pub y: Array1<f64>,
+    /// The dual slack variable $Z = C - \sum_i y_i A_i$.
+    pub z: Array2<f64>,
+    /// The primal objective value $\langle C, X \rangle$.
+    pub primal_objective: f64,
+    /// The dual objective value $b^\top y$.
+    pub dual_objective: f64,
+    /// The number of interior point iterations performed.
+    pub n_iters: usize,
+    /// Whether the stopping criteria are satisfied. The interior point method
+    /// stops early without convergence if the linear systems of the search
+    /// direction are singular, and reports the current iterate.
+    pub converged: bool,
+}
+
+impl Solution {
+    /// Extracts a vector $x$ such that $X \approx x x^\top$ from the leading
+    /// eigenpair of the primal variable, scaled so that its last entry (the
+    /// homogeneous coordinate) equals 1.
+    ///
+    /// # Returns
+    ///
+    /// The extracted vector with shape `(n, 1)`, or `None` if the homogeneous
+    /// coordinate of the leading eigenvector vanishes (relative to
+    /// [`HOMOGENEOUS_TOL`]), e.g., the relaxation is not tight.
+    pub fn rank_one_vec(&self) -> Option<Array2<f64>> {
+        let n = self.x.dim().0;
+
+        let (_, eigvecs) = self.x.eigh(UPLO::Lower).unwrap();
+        let vec = eigvecs.column(n - 1).to_owned().into_shape((n, 1)).unwrap();
+
+        // The leading eigenvector has unit norm.
+        let h = vec[[n - 1, 0]];
+        if h.abs() < HOMOGENEOUS_TOL {
+            return None;
+        }
+
+        Some((1.0 / h) * vec)
+    }
+
+    /// Computes the ratio between the second largest and the largest
+    /// eigenvalues of the primal variable. A ratio close to zero indicates that
+    /// the relaxation is tight (i.e., $X$ is of rank one).
+    ///
+    /// # Returns
+    ///
+    /// The eigenvalue ratio.
+    pub fn rank_one_ratio(&self) -> f64 {
+        let n = self.x.dim().0;
+
+        let eigvals = self.x.eigvalsh(UPLO::Lower).unwrap();
+
+        if n < 2 {
+            0.0
+        } else {
+            eigvals[n - 2].max(0.0) / eigvals[n - 1]
+        }
+    }
+}
+
+/// A small dense SDP solver based on a primal-dual interior point method.
+pub struct Solver {
+    /// The maximum number of interior point iterations allowed (default: 100).
+    pub max_iteration: usize,
+    /// The tolerance of relative duality gap and infeasibility (default: 1e-9).
+    pub tol: f64,
+}
+
+impl Default for Solver {
+    fn default() -> Self {
+        Self::new(None, None)
+    }
+}
+
+impl Solver {
+    pub fn new(max_iteration: Option<usize>, tol: Option<f64>) -> Self {
+        Self {
+            max_iteration: max_iteration.unwrap_or(100),
+            tol: tol.unwrap_or(1e-9),
+        }
+    }
+
+    /// Solve the semidefinite program.
+    ///
+    /// # Arguments
+    ///
+    /// - `c` - The symmetric cost matrix $C$.
+    /// - `a` - The symmetric constraint matrices $A_i$. They must be linearly
+    ///   independent.
+    /// - `b` - The right-hand side $b$ of the constraints.
+    ///
+    /// # Returns
+    ///
+    /// The primal and dual solutions.
+    pub fn solve(&self, c: &Array2<f64>, a: &[Array2<f64>], b: &Array1<f64>) -> Solution {
+        assert!(c.dim().0 == c.dim().1, "Cost matrix must be square");
+        assert!(
+            a.len() == b.len(),
+            "Constraints and rhs must have same size"
+        );
+
+        let n = c.dim().0;
+        let m = a.len();
+
+        // The cost matrix is normalized so that the stopping criteria do not
+        // depend on its scale.
+        let scale = frobenius(c).max(f64::MIN_POSITIVE);
+        let c = &(c / scale);
+
+        let mut x = Array2::<f64>::eye(n);
+        let mut y = Array1::<f64>::zeros(m);
+        let mut z = Array2::<f64>::eye(n);
+
+        let b_norm = 1.0 + norm(b);
+        let c_norm = 2.0;
+
+        let mut n_iters = 0;
+        let mut converged = false;
+        for _ in 0..self.max_iteration {
+            let rp = b - &apply(a, &x);
+            let rd = c - &apply_adjoint(a, &y, n) - &z;
+
+            let primal_objective = inner(c, &x);
+            let dual_objective = b.dot(&y);
+            let gap = (primal_objective - dual_objective).abs()
+                / (1.0 + primal_objective.abs() + dual_objective.abs());
+            let p_infeas = norm(&rp) / b_norm;
+            let d_infeas = frobenius(&rd) / c_norm;
+
+            if gap < self.tol && p_infeas < self.tol && d_infeas < self.tol {
+                converged = true;
+                break;
+            }
+
+            n_iters += 1;
+
+            let mu = inner(&x, &z) / n as f64;
+            let z_inv = match z.inv() {
+                Ok(z_inv) => sym(&z_inv),
+                Err(_) => break,
+            };
+
+            // Predictor (affine scaling) step.
+            let (dx_aff, _, dz_aff) = match hkm_direction(a, &x, &z_inv, &rp, &rd, 0.0, None) {
+                Some(direction) => direction,
+                None => break,
+            };
+            let alpha_p = step_length(&x, &dx_aff).min(1.0);
+            let alpha_d = step_length(&z, &dz_aff).min(1.0);
+            let mu_aff =
+                inner(&(&x + &(alpha_p * &dx_aff)), &(&z + &(alpha_d * &dz_aff))) / n as f64;
+            let sigma = (mu_aff / mu).clamp(0.0, 1.0).powi(3);
+
+            // Corrector step.
+            let (dx, dy, dz) = match hkm_direction(
+                a,
+                &x,
+                &z_inv,
+                &rp,
+                &rd,
+                sigma * mu,
+                Some((&dx_aff, &dz_aff)),
+            ) {
+                Some(direction) => direction,
+                None => break,
+            };
+
+            let alpha_p = (0.95 * step_length(&x, &dx)).min(1.0);
+            let alpha_d = (0.95 * step_length(&z, &dz)).min(1.0);
+
+            x = sym(&(&x + &(alpha_p * &dx)));
+            y = &y + &(alpha_d * &dy);
+            z = sym(&(&z + &(alpha_d * &dz)));
+        }
+
+        Solution {
+            primal_objective: scale * inner(c, &x),
+            dual_objective: scale * b.dot(&y),
+            x,
+            y: scale * y,
+            z: scale * z,
+            n_iters,
+            converged,
+        }
+    }
+
+    /// Solve the semidefinite program, and extract the rank-one vector of the
+    /// primal variable, see [`Solution::rank_one_vec`].
+    ///
+    /// # Returns
+    ///
+    /// The rank-one vector, or `None` if the interior point method does not
+    /// converge or the relaxation is not tight.
+    pub fn solve_rank_one(
+        &self,
+        c: &Array2<f64>,
+        a: &[Array2<f64>],
+        b: &Array1<f64>,
+    ) -> Option<Array2<f64>> {
+        let solution = self.solve(c, a, b);
+
+        match solution.converged {
+            true => solution.rank_one_vec(),
+            false => None,
+        }
+    }
+}
+
+/// Computes the HKM search direction. If `correction` is given, the second
+/// order term $\Delta X \Delta Z$ of the predictor step is compensated
+/// (Mehrotra's corrector). Returns `None` if the Schur complement system is
+/// singular.
+fn hkm_direction(
+    a: &[Array2<f64>],
+    x: &Array2<f64>,
+    z_inv: &Array2<f64>,
+    rp: &Array1<f64>,
+    rd: &Array2<f64>,
+    target: f64,
+    correction: Option<(&Array2<f64>, &Array2<f64>)>,
+) -> Option<(Array2<f64>, Array1<f64>, Array2<f64>)> {
+    let n = x.dim().0;
+    let m = a.len();
+
+    // The right-hand side of the complementarity equation (in the form of
+    // $\Delta X + X \Delta Z Z^{-1} = R$).
+    let mut mat_r = target * z_inv - x - x.dot(rd).dot(z_inv);
+    if let Some((dx_aff, dz_aff)) = correction {
+        mat_r = mat_r - dx_aff.dot(dz_aff).dot(z_inv);
+    }
+
+    let g: Vec<Array2<f64>> = a.iter().map(|a_j| x.dot(a_j).dot(z_inv)).collect();
+
+    let mut schur = Array2::<f64>::zeros((m, m));
+    for i in 0..m {
+        for j in i..m {
+            let value = inner(&a[i], &g[j]);
+            schur[[i, j]] = value;
+            schur[[j, i]] = value;
+        }
+    }
+
+    let rhs = rp - &apply(a, &mat_r);
+    let dy = schur.solve_into(rhs).ok()?;
+
+    let dz = rd - &apply_adjoint(a, &dy, n);
+    let dx = sym(&(mat_r + x.dot(&apply_adjoint(a, &dy, n)).dot(z_inv)));
+
+    Some((dx, dy, dz))
+}
+
+/// Computes the largest step $\alpha$ such that $X + \alpha \Delta X \succeq
+/// 0$.
+fn step_length(x: &Array2<f64>, dx: &Array2<f64>) -> f64 {
+    let (eigvals, eigvecs) = x.eigh(UPLO::Lower).unwrap();
+    let inv_sqrt = Array2::from_diag(&eigvals.mapv(|v| 1.0 / v.max(1e-300).sqrt()));
+    let x_inv_sqrt = eigvecs.dot(&inv_sqrt).dot(&eigvecs.t());
+
+    let mat = sym(&x_inv_sqrt.dot(dx).dot(&x_inv_sqrt));
+    let min_eigval = mat.eigvalsh(UPLO::Lower).unwrap()[0];
+
+    if min_eigval < 0.0 {
+        -1.0 / min_eigval
+    } else {
+        f64::INFINITY
+    }
+}
+
+fn apply(a: &[Array2<f64>], x: &Array2<f64>) -> Array1<f64> {
+    a.iter().map(|a_i| inner(a_i, x)).collect()
+}
+
+fn apply_adjoint(a: &[Array2<f64>], y: &Array1<f64>, n: usize) -> Array2<f64> {
+    let mut mat = Array2::<f64>::zeros((n, n));
+    for (a_i, y_i) in a.iter().zip(y.iter()) {
+        mat.scaled_add(*y_i, a_i);
+    }
+
+    mat
+}
+
+fn inner(a: &Array2<f64>, b: &Array2<f64>) -> f64 {
+    (a * b).sum()
+}
+
+fn norm(v: &Array1<f64>) -> f64 {
+    v.dot(v).sqrt()
+}
+
+fn frobenius(a: &Array2<f64>) -> f64 {
+    inner(a, a).sqrt()
+}
+
+fn sym(a: &Array2<f64>) -> Array2<f64> {
+    (a + &a.t()) * 0.5
+}
@@ -7,6 +7,9 @@
 
 use ndarray::{Array2, Zip};
 
+use crate::sparse::{self, CsrMatrix};
+use crate::utils;
+
 /// A trait for a type that can be used to compute the quadratic form associated
 /// with a matrix, and keep track of the most recently computed value.
 ///
@@ -47,6 +50,18 @@ pub trait R2 {
     /// The most recently computed value of the quadratic form associated with
     /// self.
     fn cache(&self) -> f64;
+
+    /// Get the entries of $x$ involved in the quadratic form. If it is `Some`,
+    /// the matrix associated with self is the submatrix over these entries,
+    /// i.e., $f(x) = x_I^\top A x_I$. The default implementation returns
+    /// `None`, i.e., the matrix spans all the entries of $x$.
+    ///
+    /// # Returns
+    ///
+    /// The indices of the involved entries of $x$, if any.
+    fn indices(&self) -> Option<&[usize]> {
+        None
+    }
 }
 
 /// A struct that implements the R2 trait using a symmetric matrix.
@@ -118,8 +133,90 @@ impl R2 for R2Sym {
     }
 }
 
+/// A struct that implements the R2 trait using a symmetric matrix over a few
+/// entries of $x$.
+///
+/// For problems whose dimension grows with the size of the input (e.g., one
+/// rotation per node of a graph), each residual only involves a few blocks of
+/// $x$. R2Sparse stores the dense submatrix $A$ over these entries, so that
+/// the memory of a term does not depend on the dimension of $x$, and the
+/// quadratic form is $f(x) = x_I^\top A x_I$.
+///
+/// # Warning
+///
+/// The matrix $A$ is assumed to be symmetric.
+pub struct R2Sparse {
+    /// The submatrix associated with self (a.k.a. $A$).
+    pub mat: Array2<f64>,
+
+    /// The indices $I$ of the entries of $x$ involved in the quadratic form.
+    pub indices: Vec<usize>,
+
+    /// The most recently computed value of the quadratic form associated with
+    /// self (a.k.a. $f(x)$).
+    cache: f64,
+}
+
+impl R2Sparse {
+    /// Creates a new instance of `R2Sparse` from a submatrix and the indices
+    /// of its rows (and columns) in $x$.
+    ///
+    /// # Arguments
+    ///
+    /// - `mat` - The submatrix associated with self (a.k.a. $A$).
+    /// - `indices` - The indices $I$ of the entries of $x$.
+    ///
+    /// # Warning
+    ///
+    /// The matrix $A$ is assumed to be symmetric.
+    pub fn new(mat: Array2<f64>, indices: Vec<usize>) -> R2Sparse {
+        assert!(
+            mat.dim() == (indices.len(), indices.len()),
+            "Submatrix must match the number of indices"
+        );
+
+        R2Sparse {
+            mat,
+            indices,
+            cache: 0.0,
+        }
+    }
+}
+
+impl R2 for R2Sparse {
+    fn call(&self, x: &Array2<f64>) -> f64 {
+        let x_sub = self
+            .indices
+            .iter()
+            .map(|&i| x[[i, 0]])
+            .collect::<ndarray::Array1<f64>>();
+
+        x_sub.dot(&self.mat.dot(&x_sub))
+    }
+
+    fn update_cache(&mut self, x: &Array2<f64>) {
+        self.cache = self.call(x);
+    }
+
+    fn mat(&self) -> &Array2<f64> {
+        &self.mat
+    }
+
+    fn cache(&self) -> f64 {
+        self.cache
+    }
+
+    fn indices(&self) -> Option<&[usize]> {
+        Some(&self.indices)
+    }
+}
+
 /// A structure to represent a fractional term $f(x)/h(x)$ in the
 /// Geman-McClure-based objective function.
+///
+/// A robust term is $f(x) = c^2 r^2(x)$ and $h(x) = r^2(x) + c^2$. A
+/// non-robust term, i.e., a residual that is never an outlier, is the plain
+/// square of residual with $f(x) = r^2(x)$ and $h(x) = 1$.
 pub struct Fractional<R> {
     /// The quadratic form associated to the square of residual.
     r2: R,
@@ -127,6 +224,8 @@ pub struct Fractional<R> {
     c: f64,
     /// The matrix used to compute the numerator.
     f_mat: Array2<f64>,
+    /// The (zero) matrix of the constant denominator of a non-robust term.
+    zero_mat: Option<Array2<f64>>,
 }
 
 impl<R: R2> Fractional<R> {
@@ -144,7 +243,39 @@ impl<R: R2> Fractional<R> {
     pub fn new(r2: R, c: f64) -> Fractional<R> {
         let f_mat = c * c * r2.mat();
 
-        Fractional { r2, c, f_mat }
+        Fractional {
+            r2,
+            c,
+            f_mat,
+            zero_mat: None,
+        }
+    }
+
+    /// Creates a new instance of `Fractional` representing a non-robust term
+    /// $r^2(x) / 1$, e.g., a measurement that is known to be an inlier.
+    ///
+    /// # Arguments
+    ///
+    /// - `r2` - The quadratic form associated to the square of residual.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `Fractional` with the given quadratic form.
+    pub fn new_non_robust(r2: R) -> Fractional<R> {
+        let f_mat = r2.mat().clone();
+        let zero_mat = Array2::zeros(f_mat.raw_dim());
+
+        Fractional {
+            r2,
+            c: 1.0,
+            f_mat,
+            zero_mat: Some(zero_mat),
+        }
+    }
+
+    /// Whether the term is robust, i.e., it may be rejected as an outlier.
+    pub fn is_robust(&self) -> bool {
+        self.zero_mat.is_none()
     }
 
     /// Updates the cache of the square of residual.
@@ -156,13 +287,25 @@ impl<R: R2> Fractional<R> {
         self.r2.update_cache(x);
     }
 
+    /// Get the most recently computed square of residual $r^2(x)$.
+    ///
+    /// # Returns
+    ///
+    /// The value of the square of residual.
+    pub fn r2(&self) -> f64 {
+        self.r2.cache()
+    }
+
     /// Computes the numerator $f(x)$.
     ///
     /// # Returns
     ///
     /// The value of the numerator $f(x)$.
     pub fn f(&self) -> f64 {
-        self.c * self.c * self.r2.cache()
+        match self.zero_mat {
+            None => self.c * self.c * self.r2.cache(),
+            Some(_) => self.r2.cache(),
+        }
     }
 
     /// Computes the denominator $h(x)$.
@@ -171,7 +314,23 @@ impl<R: R2> Fractional<R> {
     ///
     /// The value of the denominator $h(x)$.
     pub fn h(&self) -> f64 {
-        self.r2.cache() + self.c * self.c
+        match self.zero_mat {
+            None => self.r2.cache() + self.c * self.c,
+            Some(_) => 1.0,
+        }
+    }
+
+    /// Get the constant of the denominator, which is not included in the
+    /// matrix associated with the denominator.
+    ///
+    /// # Returns
+    ///
+    /// The constant $c^2$, or 1 for a non-robust term.
+    pub fn h_constant(&self) -> f64 {
+        match self.zero_mat {
+            None => self.c * self.c,
+            Some(_) => 1.0,
+        }
     }
 
     /// Get the matrix associated with the numerator.
@@ -189,19 +348,40 @@ impl<R: R2> Fractional<R> {
     ///
     /// A reference to the matrix associated with the denominator.
     pub fn h_mat(&self) -> &Array2<f64> {
-        self.r2.mat()
+        match &self.zero_mat {
+            None => self.r2.mat(),
+            Some(zero_mat) => zero_mat,
+        }
+    }
+
+    /// Get the entries of $x$ involved in the term. See [`R2::indices`].
+    ///
+    /// # Returns
+    ///
+    /// The indices of the involved entries of $x$, if any.
+    pub fn indices(&self) -> Option<&[usize]> {
+        self.r2.indices()
     }
 }
 
 /// A trait for a type that can be used to implement the FracGM solver.
 ///
 /// This trait defines the methods that the FracGM solver needs to implement.
+/// The measurements of a problem are described by the associated type `Input`
+/// (e.g., a pair of point clouds), and its solution by the associated type
+/// `Output` (e.g., a rotation matrix).
 pub trait FractionalProgrammingMaterials<R: R2> {
-    /// Get the dimension of the point clouds.
+    /// The measurements consumed by the solver.
+    type Input<'a>;
+
+    /// The solution returned by the solver.
+    type Output;
+
+    /// Get the dimension of the lifted variable.
     ///
     /// # Returns
     ///
-    /// The dimension of the point clouds.
+    /// The dimension of the lifted variable.
     fn dim(&self) -> usize;
 
     /// Get the maximum number of iterations allowed.
@@ -225,18 +405,18 @@ pub trait FractionalProgrammingMaterials<R: R2> {
     /// The value of $c$.
     fn c(&self) -> f64;
 
-    /// Convert an input matrix to a flattened vector.
+    /// Convert a solution to a flattened vector.
     ///
     /// # Arguments
     ///
-    /// - `mat` - The input matrix.
+    /// - `mat` - The solution.
     ///
     /// # Returns
     ///
-    /// The vector representation of the matrix.
-    fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64>;
+    /// The vector representation of the solution.
+    fn mat_to_vec(&self, mat: &Self::Output) -> Array2<f64>;
 
-    /// Convert a flattened vector to a matrix.
+    /// Convert a flattened vector to a solution.
     ///
     /// # Arguments
     ///
@@ -244,48 +424,83 @@ pub trait FractionalProgrammingMaterials<R: R2> {
     ///
     /// # Returns
     ///
-    /// The matrix representation of the vector.
-    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64>;
+    /// The solution represented by the vector.
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Self::Output;
 
     /// Project the solution to the original variable space.
     ///
     /// # Arguments
     ///
-    /// - `mat` - The input matrix.
+    /// - `mat` - The solution.
     ///
     /// # Returns
     ///
-    /// The projected matrix.
-    fn project(&self, mat: &Array2<f64>) -> Array2<f64>;
+    /// The projected solution.
+    fn project(&self, mat: &Self::Output) -> Self::Output;
+
+    /// Check if the input is valid for the solver. The default implementation
+    /// accepts any input.
+    ///
+    /// # Arguments
+    ///
+    /// - `input` - The measurements.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the input is invalid.
+    fn check_input(&self, _input: &Self::Input<'_>) {}
 
     /// Compute the Geman-McClure terms for the FracGM solver.
     ///
     /// # Arguments
     ///
-    /// - `pc1` - The source point cloud.
-    /// - `pc2` - The target point cloud.
+    /// - `input` - The measurements.
     ///
     /// # Returns
     ///
     /// The computed Geman-McClure terms.
-    fn compute_terms(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Vec<Fractional<R>>;
+    fn compute_terms(&self, input: &Self::Input<'_>) -> Vec<Fractional<R>>;
 
     /// Compute the initial guess of the solution. In general a regular least
     /// squares solution is acceptable.
     ///
     /// # Arguments
     ///
-    /// - `pc1` - The source point cloud.
-    /// - `pc2` - The target point cloud.
+    /// - `input` - The measurements.
     ///
     /// # Returns
     ///
     /// The computed initial guess.
-    fn compute_initial_guess(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64>;
+    fn compute_initial_guess(&self, input: &Self::Input<'_>) -> Self::Output;
+
+    /// Compute the least squares solution of the terms, i.e., the solution of
+    /// the subproblem $\min_x \sum_i x^\top H_i x$ with unit weights, which
+    /// serves as the initial guess of most solvers.
+    ///
+    /// # Arguments
+    ///
+    /// - `terms` - The computed terms.
+    ///
+    /// # Returns
+    ///
+    /// The least squares solution (not projected).
+    fn compute_least_squares_initial_guess(&self, terms: &[Fractional<R>]) -> Self::Output {
+        let mut mat = Array2::<f64>::zeros((self.dim(), self.dim()));
+        for term in terms.iter() {
+            mat += term.h_mat();
+        }
+
+        self.vec_to_mat(&self.solve_x(&mat))
+    }
 
     /// Solve the underlying convex optimization problem defined in the FracGM
     /// solver.
     ///
+    /// The default implementation solves the homogeneous linear system $A y =
+    /// e$, where $e$ is the last standard basis vector, and rescales $y$ by its
+    /// last entry (the Schur complement) so that the homogeneous coordinate of
+    /// the solution equals 1.
+    ///
     /// # Arguments
     ///
     /// - `mat` - The input matrix.
@@ -293,7 +508,123 @@ pub trait FractionalProgrammingMaterials<R: R2> {
     /// # Returns
     ///
     /// The solution of the convex optimization problem.
-    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64>;
+    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
+        utils::solve_homogeneous(mat)
+    }
+
+    /// Compute the matrix $A = \sum_i \mu_i (F_i - \beta_i H_i)$ of the
+    /// convex subproblem, where $F_i$ and $H_i$ are the matrices associated with
+    /// the numerator and the denominator of the $i$-th term. The submatrices of
+    /// sparse terms (see [`R2Sparse`]) are scattered to their entries.
+    ///
+    /// # Arguments
+    ///
+    /// - `beta` - The auxiliary variable $\beta$.
+    /// - `mu` - The auxiliary variable $\mu$.
+    /// - `terms` - The computed Geman-McClure terms.
+    ///
+    /// # Returns
+    ///
+    /// The matrix of the convex subproblem.
+    fn compute_mat_a(&self, beta: &[f64], mu: &[f64], terms: &[Fractional<R>]) -> Array2<f64> {
+        assert!(beta.len() == mu.len());
+        assert!(beta.len() == terms.len());
+
+        let mut mat_a = Array2::<f64>::zeros((self.dim(), self.dim()));
+        for i in 0..terms.len() {
+            let mu_ = &mu[i];
+            let beta_ = &beta[i];
+            match terms[i].indices() {
+                None => Zip::from(&mut mat_a)
+                    .and(terms[i].f_mat())
+                    .and(terms[i].h_mat())
+                    .for_each(|a, f, h| {
+                        *a += mu_ * f - mu_ * beta_ * h;
+                    }),
+                Some(indices) => Zip::indexed(terms[i].f_mat())
+                    .and(terms[i].h_mat())
+                    .for_each(|(p, q), f, h| {
+                        mat_a[[indices[p], indices[q]]] += mu_ * f - mu_ * beta_ * h;
+                    }),
+            }
+        }
+
+        mat_a
+    }
+
+    /// Whether the convex subproblems are assembled and solved with the sparse
+    /// backend of [`crate::sparse`] instead of dense matrices. It pays off for
+    /// problems with thousands of unknowns whose terms are sparse (see
+    /// [`R2Sparse`]). The default implementation returns `false`.
+    ///
+    /// # Returns
+    ///
+    /// Whether the sparse backend is used.
+    fn use_sparse(&self) -> bool {
+        false
+    }
+
+    /// Compute the matrix $A = \sum_i \mu_i (F_i - \beta_i H_i)$ of the
+    /// convex subproblem in CSR format, see [`Self::compute_mat_a`].
+    ///
+    /// # Arguments
+    ///
+    /// - `beta` - The auxiliary variable $\beta$.
+    /// - `mu` - The auxiliary variable $\mu$.
+    /// - `terms` - The computed Geman-McClure terms.
+    ///
+    /// # Returns
+    ///
+    /// The sparse matrix of the convex subproblem.
+    fn compute_sparse_mat_a(&self, beta: &[f64], mu: &[f64], terms: &[Fractional<R>]) -> CsrMatrix {
+        sparse::compute_mat_a(self.dim(), beta, mu, terms)
+    }
+
+    /// Solve the underlying convex optimization problem with a sparse matrix,
+    /// see [`Self::solve_x`].
+    ///
+    /// The default implementation solves the homogeneous system with the
+    /// conjugate gradient method warm-started from the previous solution,
+    /// which requires the matrix without its last row and column to be
+    /// positive definite, e.g., the gauge freedom of the problem is removed.
+    ///
+    /// # Arguments
+    ///
+    /// - `mat` - The sparse matrix of the convex subproblem.
+    /// - `vec` - The previous solution.
+    ///
+    /// # Returns
+    ///
+    /// The solution of the convex optimization problem.
+    fn solve_x_sparse(&self, mat: &CsrMatrix, vec: &Array2<f64>) -> Array2<f64> {
+        sparse::solve_homogeneous(mat, Some(vec), sparse::CG_TOL, 10 * self.dim())
+    }
+
+    /// Solve the convex subproblem given the auxiliary variables, with either
+    /// the dense or the sparse backend (see [`Self::use_sparse`]).
+    ///
+    /// # Arguments
+    ///
+    /// - `beta` - The auxiliary variable $\beta$.
+    /// - `mu` - The auxiliary variable $\mu$.
+    /// - `terms` - The computed Geman-McClure terms.
+    /// - `vec` - The previous solution.
+    ///
+    /// # Returns
+    ///
+    /// The solution of the convex subproblem.
+    fn solve_subproblem(
+        &self,
+        beta: &[f64],
+        mu: &[f64],
+        terms: &[Fractional<R>],
+        vec: &Array2<f64>,
+    ) -> Array2<f64> {
+        match self.use_sparse() {
+            true => self.solve_x_sparse(&self.compute_sparse_mat_a(beta, mu, terms), vec),
+            false => self.solve_x(&self.compute_mat_a(beta, mu, terms)),
+        }
+    }
 
     /// Solve the underlying linear system for auxilary variables $\beta$ and
     /// $\mu$.
@@ -305,7 +636,7 @@ pub trait FractionalProgrammingMaterials<R: R2> {
     /// # Returns
     ///
     /// The solution of the linear system.
-    fn solve_beta_mu(&self, terms: &Vec<Fractional<R>>) -> (Vec<f64>, Vec<f64>) {
+    fn solve_beta_mu(&self, terms: &[Fractional<R>]) -> (Vec<f64>, Vec<f64>) {
         let beta = terms.iter().map(|term| term.f() / term.h()).collect();
         let mu = terms.iter().map(|term| 1.0 / term.h()).collect();
 
@@ -324,7 +655,7 @@ pub trait FractionalProgrammingMaterials<R: R2> {
     /// # Returns
     ///
     /// The norm of the vector $psi$.
-    fn compute_psi_norm(&self, beta: &Vec<f64>, mu: &Vec<f64>, terms: &Vec<Fractional<R>>) -> f64 {
+    fn compute_psi_norm(&self, beta: &[f64], mu: &[f64], terms: &[Fractional<R>]) -> f64 {
         assert!(beta.len() == mu.len());
         assert!(beta.len() == terms.len());
 
@@ -334,7 +665,7 @@ pub trait FractionalProgrammingMaterials<R: R2> {
                 let f = term.f();
                 let h = term.h();
 
-                let a = -1.0 * f + beta_ * h;
+                let a = -f + beta_ * h;
                 let b = -1.0 + mu_ * h;
                 a * a + b * b
             })
@@ -360,44 +691,39 @@ pub trait GemanMcclureSolver<R: R2>: FractionalProgrammingMaterials<R> {
     ///
     /// # Arguments
     ///
-    /// - `pc1` - The source point cloud.
-    /// - `pc2` - The target point cloud.
+    /// - `input` - The measurements.
     ///
     /// # Returns
     ///
     /// The solution of the optimization problem.
-    fn solve(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
-        assert!(
-            pc1.shape() == pc2.shape(),
-            "Input point clouds must have the same shape"
-        );
-        assert!(
-            pc1.shape()[1] == 3,
-            "Input point clouds must have 3 columns"
-        );
+    fn solve(&self, input: Self::Input<'_>) -> Self::Output {
+        self.check_input(&input);
+
+        let terms = self.compute_terms(&input);
+        let init_mat = self.compute_initial_guess(&input);
 
-        let mut terms = self.compute_terms(pc1, pc2);
+        self.solve_terms(terms, &init_mat)
+    }
 
-        let init_mat = self.compute_initial_guess(pc1, pc2);
-        let mut vec = self.mat_to_vec(&init_mat);
+    /// Solve the Geman-McClure-based objective function with the FracGM solver
+    /// from precomputed Geman-McClure terms.
+    ///
+    /// # Arguments
+    ///
+    /// - `terms` - The computed Geman-McClure terms.
+    /// - `init_mat` - The initial guess.
+    ///
+    /// # Returns
+    ///
+    /// The solution of the optimization problem.
+    fn solve_terms(&self, mut terms: Vec<Fractional<R>>, init_mat: &Self::Output) -> Self::Output {
+        let mut vec = self.mat_to_vec(init_mat);
         self.update_terms_cache(&mut terms, &vec);
 
         let (mut beta, mut mu) = self.solve_beta_mu(&terms);
 
         for _ in 0..self.max_iteration() {
-            let mut mat_a = Array2::<f64>::zeros((self.dim(), self.dim()));
-            for i in 0..pc1.dim().0 {
-                let mu_ = &mu[i];
-                let beta_ = &beta[i];
-                Zip::from(&mut mat_a)
-                    .and(terms[i].f_mat())
-                    .and(terms[i].h_mat())
-                    .for_each(|a, f, h| {
-                        *a += mu_ * f - mu_ * beta_ * h;
-                    });
-            }
-
-            vec = self.solve_x(&mat_a);
+            vec = self.solve_subproblem(&beta, &mu, &terms, &vec);
             self.update_terms_cache(&mut terms, &vec);
 
             let psi_norm = self.compute_psi_norm(&beta, &mu, &terms);
@@ -414,13 +740,13 @@ pub trait GemanMcclureSolver<R: R2>: FractionalProgrammingMaterials<R> {
 
 /// Component of the diagnostic information.
 #[derive(Clone)]
-pub struct IterationComponent {
+pub struct IterationComponent<T = Array2<f64>> {
     /// The current solution in vector form.
     pub alpha_vec: Array2<f64>,
     /// The current solution in matrix form.
-    pub alpha_mat: Array2<f64>,
+    pub alpha_mat: T,
     /// The current solution projected to the original variable space.
-    pub alpha_proj: Array2<f64>,
+    pub alpha_proj: T,
     /// The current value of the auxiliary variable $\beta$.
     pub beta: Vec<f64>,
     /// The current value of the auxiliary variable $\mu$.
@@ -432,11 +758,11 @@ pub struct IterationComponent {
 
 /// The diagnostic information returned by the FracGM solver.
 #[derive(Clone)]
-pub struct Diagnostic {
+pub struct Diagnostic<T = Array2<f64>> {
     /// The diagnostic information of each iteration.
-    pub iterations: Vec<IterationComponent>,
+    pub iterations: Vec<IterationComponent<T>>,
     /// The final solution of the optimization problem.
-    pub solution: Array2<f64>,
+    pub solution: T,
     /// The number of iterations performed by the solver.
     pub n_iters: usize,
 }
@@ -457,19 +783,19 @@ pub trait GemanMcclureSolverDiagnostic<R: R2>: FractionalProgrammingMaterials<R>
     fn update_diagnostics(
         &self,
         alpha: &Array2<f64>,
-        beta: &Vec<f64>,
-        mu: &Vec<f64>,
-        terms: &Vec<Fractional<R>>,
-        diagnostics: &mut Vec<IterationComponent>,
+        beta: &[f64],
+        mu: &[f64],
+        terms: &[Fractional<R>],
+        diagnostics: &mut Vec<IterationComponent<Self::Output>>,
     ) {
-        let psi_norm = self.compute_psi_norm(&beta, &mu, &terms);
+        let psi_norm = self.compute_psi_norm(beta, mu, terms);
 
         let component = IterationComponent {
             alpha_vec: alpha.clone(),
-            alpha_mat: self.vec_to_mat(&alpha),
-            alpha_proj: self.project(&self.vec_to_mat(&alpha)),
-            beta: beta.clone(),
-            mu: mu.clone(),
+            alpha_mat: self.vec_to_mat(alpha),
+            alpha_proj: self.project(&self.vec_to_mat(alpha)),
+            beta: beta.to_vec(),
+            mu: mu.to_vec(),
             psi_norm,
         };
 
@@ -481,28 +807,39 @@ pub trait GemanMcclureSolverDiagnostic<R: R2>: FractionalProgrammingMaterials<R>
     ///
     /// # Arguments
     ///
-    /// - `pc1` - The source point cloud.
-    /// - `pc2` - The target point cloud.
+    /// - `input` - The measurements.
     ///
     /// # Returns
     ///
     /// The solution of the optimization problem and the diagnostic information.
-    fn solve(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Diagnostic {
-        assert!(
-            pc1.shape() == pc2.shape(),
-            "Input point clouds must have the same shape"
-        );
-        assert!(
-            pc1.shape()[1] == 3,
-            "Input point clouds must have 3 columns"
-        );
+    fn solve(&self, input: Self::Input<'_>) -> Diagnostic<Self::Output> {
+        self.check_input(&input);
+
+        let terms = self.compute_terms(&input);
+        let init_mat = self.compute_initial_guess(&input);
 
-        let mut iterations: Vec<IterationComponent> = Vec::new();
+        self.solve_terms(terms, &init_mat)
+    }
 
-        let mut terms = self.compute_terms(pc1, pc2);
+    /// Solve and diagnose the Geman-McClure-based objective function with the
+    /// FracGM solver from precomputed Geman-McClure terms.
+    ///
+    /// # Arguments
+    ///
+    /// - `terms` - The computed Geman-McClure terms.
+    /// - `init_mat` - The initial guess.
+    ///
+    /// # Returns
+    ///
+    /// The solution of the optimization problem and the diagnostic information.
+    fn solve_terms(
+        &self,
+        mut terms: Vec<Fractional<R>>,
+        init_mat: &Self::Output,
+    ) -> Diagnostic<Self::Output> {
+        let mut iterations: Vec<IterationComponent<Self::Output>> = Vec::new();
 
-        let init_mat = self.compute_initial_guess(pc1, pc2);
-        let mut vec = self.mat_to_vec(&init_mat);
+        let mut vec = self.mat_to_vec(init_mat);
         self.update_terms_cache(&mut terms, &vec);
 
         let (mut beta, mut mu) = self.solve_beta_mu(&terms);
@@ -513,19 +850,7 @@ pub trait GemanMcclureSolverDiagnostic<R: R2>: FractionalProgrammingMaterials<R>
         for _ in 0..self.max_iteration() {
             n_iters += 1;
 
-            let mut mat_a = Array2::<f64>::zeros((self.dim(), self.dim()));
-            for i in 0..pc1.dim().0 {
-                let mu_ = &mu[i];
-                let beta_ = &beta[i];
-                Zip::from(&mut mat_a)
-                    .and(terms[i].f_mat())
-                    .and(terms[i].h_mat())
-                    .for_each(|a, f, h| {
-                        *a += mu_ * f - mu_ * beta_ * h;
-                    });
-            }
-
-            vec = self.solve_x(&mat_a);
+            vec = self.solve_subproblem(&beta, &mu, &terms, &vec);
             self.update_terms_cache(&mut terms, &vec);
 
             let psi_norm = self.compute_psi_norm(&beta, &mu, &terms);
@@ -0,0 +1,331 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains a small sparse linear algebra backend for FracGM
+//! problems whose dimension grows with the size of the input, e.g., graphs of
+//! poses. The matrix $A = \sum_i \mu_i (F_i - \beta_i H_i)$ is assembled in
+//! compressed sparse row (CSR) format from the terms, and the homogeneous
+//! system is solved with the Jacobi-preconditioned conjugate gradient method.
+//!
+//! A solver opts in the sparse backend with
+//! [`crate::solver::FractionalProgrammingMaterials::use_sparse`].
+
+use ndarray::{Array1, Array2};
+
+use crate::solver::{Fractional, R2};
+
+/// The default tolerance of the relative residual of the conjugate gradient
+/// method.
+pub const CG_TOL: f64 = 1e-10;
+
+/// A square sparse matrix in compressed sparse row (CSR) format.
+#[derive(Clone)]
+pub struct CsrMatrix {
+    /// The number of rows (and columns).
+    pub dim: usize,
+    /// The offsets of the rows in `indices` and `values`.
+    pub indptr: Vec<usize>,
+    /// The column indices of the stored entries.
+    pub indices: Vec<usize>,
+    /// The values of the stored entries.
+    pub values: Vec<f64>,
+}
+
+impl CsrMatrix {
+    /// Creates a new instance of `CsrMatrix` from triplets `(row, col,
+    /// value)`. Duplicated entries are summed.
+    ///
+    /// # Arguments
+    ///
+    /// - `dim` - The number of rows (and columns).
+    /// - `triplets` - The entries of the matrix.
+    ///
+    /// # Returns
+    ///
+    /// A new instance of `CsrMatrix`.
+    pub fn from_triplets(dim: usize, mut triplets: Vec<(usize, usize, f64)>) -> CsrMatrix {
+        assert!(
+            triplets.iter().all(|&(row, col, _)| row < dim && col < dim),
+            "Triplets must be within the matrix"
+        );
+
+        triplets.sort_unstable_by_key(|&(row, col, _)| (row, col));
+
+        let mut indptr = vec![0; dim + 1];
+        let mut indices: Vec<usize> = Vec::with_capacity(triplets.len());
+        let mut values: Vec<f64> = Vec::with_capacity(triplets.len());
+        let mut last: Option<(usize, usize)> = None;
+        for (row, col, value) in triplets {
+            if last == Some((row, col)) {
+                *values.last_mut().unwrap() += value;
+                continue;
+            }
+            indptr[row + 1] += 1;
+            indices.push(col);
+            values.push(value);
+            last = Some((row, col));
+        }
+        for row in 0..dim {
+            indptr[row + 1] += indptr[row];
+        }
+
+        CsrMatrix {
+            dim,
+            indptr,
+            indices,
+            values,
+        }
+    }
+
+    /// Creates a new instance of `CsrMatrix` from the nonzero entries of a
+    /// dense matrix.
+    pub fn from_dense(mat: &Array2<f64>) -> CsrMatrix {
+        assert!(mat.dim().0 == mat.dim().1, "Matrix must be square");
+
+        let triplets = mat
+            .indexed_iter()
+            .filter(|(_, value)| **value != 0.0)
+            .map(|((row, col), value)| (row, col, *value))
+            .collect();
+
+        CsrMatrix::from_triplets(mat.dim().0, triplets)
+    }
+
+    /// Get the number of stored entries.
+    pub fn nnz(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Computes the matrix-vector product $A x$.
+    pub fn dot(&self, x: &Array1<f64>) -> Array1<f64> {
+        assert!(x.len() == self.dim, "Vector must match the matrix");
+
+        Array1::from_iter((0..self.dim).map(|row| {
+            (self.indptr[row]..self.indptr[row + 1])
+                .map(|k| self.values[k] * x[self.indices[k]])
+                .sum::<f64>()
+        }))
+    }
+
+    /// Get the diagonal of the matrix.
+    pub fn diag(&self) -> Array1<f64> {
+        Array1::from_iter((0..self.dim).map(|row| {
+            (self.indptr[row]..self.indptr[row + 1])
+                .filter(|&k| self.indices[k] == row)
+                .map(|k| self.values[k])
+                .sum::<f64>()
+        }))
+    }
+
+    /// Converts the matrix to a dense matrix.
+    pub fn to_dense(&self) -> Array2<f64> {
+        let mut mat = Array2::<f64>::zeros((self.dim, self.dim));
+        for row in 0..self.dim {
+            for k in self.indptr[row]..self.indptr[row + 1] {
+                mat[[row, self.indices[k]]] += self.values[k];
+            }
+        }
+
+        mat
+    }
+}
+
+/// Assembles the matrix $A = \sum_i \mu_i (F_i - \beta_i H_i)$ of the convex
+/// subproblem in CSR format. The submatrices of sparse terms (see
+/// [`crate::solver::R2Sparse`]) are scattered to their entries, while the
+/// matrices of dense terms cover the whole variable.
+///
+/// # Arguments
+///
+/// - `dim` - The dimension of the lifted variable.
+/// - `beta` - The auxiliary variable $\beta$.
+/// - `mu` - The auxiliary variable $\mu$.
+/// - `terms` - The computed Geman-McClure terms.
+///
+/// # Returns
+///
+/// The matrix of the convex subproblem.
+pub fn compute_mat_a<R: R2>(
+    dim: usize,
+    beta: &[f64],
+    mu: &[f64],
+    terms: &[Fractional<R>],
+) -> CsrMatrix {
+    assert!(beta.len() == mu.len());
+    assert!(beta.len() == terms.len());
+
+    let nnz = terms.iter().map(|term| term.f_mat().len()).sum::<usize>();
+
+    let mut triplets: Vec<(usize, usize, f64)> = Vec::with_capacity(nnz);
+
+    for ((beta_, mu_), term) in beta.iter().zip(mu.iter()).zip(terms.iter()) {
+        for (((p, q), f), h) in term.f_mat().indexed_iter().zip(term.h_mat().iter()) {
+            let value = mu_ * f - mu_ * beta_ * h;
+            if value == 0.0 {
+                continue;
+            }
+            match term.indices() {
+                None => triplets.push((p, q, value)),
+                Some(indices) => triplets.push((indices[p], indices[q], value)),
+            }
+        }
+    }
+
+    CsrMatrix::from_triplets(dim, triplets)
+}
+
+/// Solves the homogeneous quadratic program $\min_x x^\top A x$ subject to
+/// $x_n = 1$ with the Jacobi-preconditioned conjugate gradient method. It is
+/// the same solution as [`crate::utils::solve_homogeneous`], provided that the
+/// matrix $A$ without its last row and column is positive definite.
+///
+/// # Arguments
+///
+/// - `mat` - The matrix $A$.
+/// - `init` - The initial guess $x$ with shape `(dim, 1)` (warm start).
+/// - `tol` - The tolerance of the relative residual.
+/// - `max_iteration` - The maximum number of iterations.
+///
+/// # Returns
+///
+/// The solution with shape `(dim, 1)`.
+pub fn solve_homogeneous(
+    mat: &CsrMatrix,
+    init: Option<&Array2<f64>>,
+    tol: f64,
+    max_iteration: usize,
+) -> Array2<f64> {
+    let dim = mat.dim;
+
+    let mut vec = match init {
+        Some(init) => init.clone(),
+        None => Array2::<f64>::zeros((dim, 1)),
+    };
+    vec[[dim - 1, 0]] = 1.0;
+
+    let free = (0..dim - 1).collect::<Vec<usize>>();
+
+    solve_free(mat, &vec, &free, tol, max_iteration)
+}
+
+/// Minimizes $x^\top A x$ over the entries $x_F$ of `free`, where the other
+/// entries are fixed to those of `vec`, with the Jacobi-preconditioned
+/// conjugate gradient method. The solution satisfies $A_{FF} x_F = -A_{FG}
+/// x_G$, where $G$ is the complement of $F$, and $A_{FF}$ is assumed to be
+/// positive definite. The entries of `vec` in $F$ are the initial guess.
+///
+/// # Arguments
+///
+/// - `mat` - The matrix $A$.
+/// - `vec` - The fixed entries and the initial guess with shape `(dim, 1)`.
+/// - `free` - The indices $F$ of the free entries.
+/// - `tol` - The tolerance of the relative residual.
+/// - `max_iteration` - The maximum number of iterations.
+///
+/// # Returns
+///
+/// The solution with shape `(dim, 1)`.
+pub fn solve_free(
+    mat: &CsrMatrix,
+    vec: &Array2<f64>,
+    free: &[usize],
+    tol: f64,
+    max_iteration: usize,
+) -> Array2<f64> {
+    let dim = mat.dim;
+    assert!(vec.dim() == (dim, 1), "Vector must match the matrix");
+
+    let gather = |x: &Array1<f64>| Array1::from_iter(free.iter().map(|&k| x[k]));
+    let scatter = |z: &Array1<f64>, x: &mut Array1<f64>| {
+        for (&k, &value) in free.iter().zip(z.iter()) {
+            x[k] = value;
+        }
+    };
+
+    // Apply $A_{FF}$ by padding the other entries with zero.
+    let apply = |z: &Array1<f64>| -> Array1<f64> {
+        let mut x = Array1::<f64>::zeros(dim);
+        scatter(z, &mut x);
+        gather(&mat.dot(&x))
+    };
+
+    let mut fixed = vec.column(0).to_owned();
+    let z = gather(&fixed);
+    scatter(&Array1::zeros(free.len()), &mut fixed);
+
+    let rhs = gather(&mat.dot(&fixed)).mapv(|x| -x);
+    let z = pcg(apply, &gather(&mat.diag()), &rhs, z, tol, max_iteration);
+
+    scatter(&z, &mut fixed);
+
+    fixed.into_shape((dim, 1)).unwrap()
+}
+
+/// Solves the linear system $A x = b$ with the Jacobi-preconditioned conjugate
+/// gradient method, where $A$ is assumed to be positive definite.
+///
+/// # Arguments
+///
+/// - `mat` - The matrix $A$.
+/// - `rhs` - The right-hand side $b$.
+/// - `init` - The initial guess $x$ (warm start).
+/// - `tol` - The tolerance of the relative residual.
+/// - `max_iteration` - The maximum number of iterations.
+///
+/// # Returns
+///
+/// The solution $x$.
+pub fn solve(
+    mat: &CsrMatrix,
+    rhs: &Array1<f64>,
+    init: Option<&Array1<f64>>,
+    tol: f64,
+    max_iteration: usize,
+) -> Array1<f64> {
+    assert!(rhs.len() == mat.dim, "Vector must match the matrix");
+
+    let init = match init {
+        Some(init) => init.clone(),
+        None => Array1::<f64>::zeros(mat.dim),
+    };
+
+    pcg(|x| mat.dot(x), &mat.diag(), rhs, init, tol, max_iteration)
+}
+
+/// The Jacobi-preconditioned conjugate gradient method of the operator
+/// `apply` with diagonal `diag`, starting from `z`.
+fn pcg<F: Fn(&Array1<f64>) -> Array1<f64>>(
+    apply: F,
+    diag: &Array1<f64>,
+    rhs: &Array1<f64>,
+    mut z: Array1<f64>,
+    tol: f64,
+    max_iteration: usize,
+) -> Array1<f64> {
+    let precond = diag.mapv(|x| if x > 0.0 { 1.0 / x } else { 1.0 });
+
+    let rhs_norm = rhs.dot(rhs).sqrt();
+    let mut r = rhs - &apply(&z);
+    let mut y = &precond * &r;
+    let mut p = y.clone();
+    let mut ry = r.dot(&y);
+
+    for _ in 0..max_iteration {
+        if r.dot(&r).sqrt() <= tol * rhs_norm.max(f64::MIN_POSITIVE) {
+            break;
+        }
+
+        let ap = apply(&p);
+        let alpha = ry / p.dot(&ap);
+        z.scaled_add(alpha, &p);
+        r.scaled_add(-alpha, &ap);
+
+        y = &precond * &r;
+        let ry_new = r.dot(&y);
+        p = &y + &(ry_new / ry * &p);
+        ry = ry_new;
+    }
+
+    z
+}
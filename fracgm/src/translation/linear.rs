@@ -4,13 +4,12 @@
 
 use ndarray::prelude::*;
 use ndarray::Array2;
-use ndarray_linalg::*;
 
+use crate::manifold::{Euclidean, Manifold};
 use crate::solver::{
     Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
     R2Sym,
 };
-use crate::translation::utils as trans_utils;
 use crate::utils;
 
 /// FracGM-based translation solver with linear (naive) relaxation.
@@ -23,6 +22,8 @@ pub struct LinearSolver {
     pub noise_bound: f64,
     /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
     pub c: f64,
+    /// The dimension of the translation (default: 3).
+    pub n_dims: usize,
 }
 
 impl LinearSolver {
@@ -32,13 +33,21 @@ impl LinearSolver {
             tol,
             noise_bound: noise_bound.unwrap_or(0.1),
             c: c.unwrap_or(1.0),
+            n_dims: 3,
         }
     }
+
+    pub fn set_n_dims(&mut self, n_dims: usize) {
+        self.n_dims = n_dims;
+    }
 }
 
 impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = (&'a Array2<f64>, &'a Array2<f64>);
+    type Output = Array2<f64>;
+
     fn dim(&self) -> usize {
-        trans_utils::DIM
+        self.n_dims + 1
     }
 
     fn max_iteration(&self) -> usize {
@@ -54,39 +63,38 @@ impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
     }
 
     fn mat_to_vec(&self, mat: &Array2<f64>) -> Array2<f64> {
-        let mut vec = Array2::<f64>::zeros((trans_utils::DIM, 1));
-        vec.slice_mut(s![0..3, ..]).assign(mat);
-        vec[[3, 0]] = 1.0;
+        let mut vec = Array2::<f64>::zeros((self.dim(), 1));
+        vec.slice_mut(s![0..self.n_dims, ..]).assign(mat);
+        vec[[self.n_dims, 0]] = 1.0;
 
         vec
     }
 
     fn vec_to_mat(&self, vec: &Array2<f64>) -> Array2<f64> {
-        vec.slice(s![0..3, ..]).to_owned()
+        vec.slice(s![0..self.n_dims, ..]).to_owned()
     }
 
     fn project(&self, mat: &Array2<f64>) -> Array2<f64> {
-        mat.clone()
+        Euclidean.project(mat)
     }
 
-    fn compute_terms(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Vec<Fractional<R2Sym>> {
-        let mut terms: Vec<Fractional<R2Sym>> = Vec::new();
-        terms.reserve(pc1.dim().0);
+    fn check_input(&self, (pc1, pc2): &Self::Input<'_>) {
+        utils::check_point_cloud_pair(pc1, pc2, self.n_dims);
+    }
+
+    fn compute_terms(&self, (pc1, pc2): &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(pc1.dim().0);
 
+        let n = self.n_dims;
         for i in 0..pc1.dim().0 {
-            let mut mat_m = Array2::<f64>::eye(trans_utils::DIM);
+            let mut mat_m = Array2::<f64>::eye(self.dim());
 
             let diff = &pc1.row(i) - &pc2.row(i);
 
-            mat_m[[0, 3]] = diff[0];
-            mat_m[[1, 3]] = diff[1];
-            mat_m[[2, 3]] = diff[2];
-
-            mat_m[[3, 0]] = diff[0];
-            mat_m[[3, 1]] = diff[1];
-            mat_m[[3, 2]] = diff[2];
+            mat_m.slice_mut(s![0..n, n]).assign(&diff);
+            mat_m.slice_mut(s![n, 0..n]).assign(&diff);
 
-            mat_m[[3, 3]] = diff[0] * diff[0] + diff[1] * diff[1] + diff[2] * diff[2];
+            mat_m[[n, n]] = diff.dot(&diff);
 
             terms.push(Fractional::new(R2Sym::new(mat_m), self.c()));
         }
@@ -94,28 +102,15 @@ impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
         terms
     }
 
-    fn compute_initial_guess(&self, pc1: &Array2<f64>, pc2: &Array2<f64>) -> Array2<f64> {
+    fn compute_initial_guess(&self, (pc1, pc2): &Self::Input<'_>) -> Array2<f64> {
         let (_, mean1) = &utils::get_zero_mean_point_cloud(pc1);
         let (_, mean2) = &utils::get_zero_mean_point_cloud(pc2);
 
-        let mut trans = Array2::<f64>::zeros((3, 1));
+        let mut trans = Array2::<f64>::zeros((self.n_dims, 1));
         trans.slice_mut(s![.., 0]).assign(&(mean2 - mean1));
 
         trans
     }
-
-    fn solve_x(&self, mat: &Array2<f64>) -> Array2<f64> {
-        let e = array![0.0, 0.0, 0.0, 1.0];
-
-        let lu_factor = mat.clone().factorize_into().unwrap();
-
-        let y = lu_factor.solve_into(e).unwrap().into_shape((4, 1)).unwrap();
-        let schur = y[[3, 0]];
-
-        let x = (1.0 / schur) * y;
-
-        x
-    }
 }
 
 impl GemanMcclureSolver<R2Sym> for LinearSolver {}
@@ -0,0 +1,18 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+//! This module contains the FracGM solver of robust multi-view triangulation.
+//! Given the observations of a 3D point $X$ in several views, either pixels of
+//! cameras with projection matrices or bearing vectors of cameras with poses,
+//! it estimates $X$ from the offsets of $X$ from the viewing rays, which are
+//! linear in the homogeneous point $[X; 1]$ like the DLT residuals. Each offset
+//! is divided by the distance of a reference point to the camera center, so
+//! that the residuals approximate the angular reprojection errors and the
+//! inlier test does not grow more permissive with depth.
+
+pub mod linear;
+pub mod utils;
+
+pub use linear::LinearSolver;
+pub use utils::{Observation, Triangulation};
@@ -0,0 +1,235 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use std::thread;
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+
+use crate::solver::{
+    Fractional, FractionalProgrammingMaterials, GemanMcclureSolver, GemanMcclureSolverDiagnostic,
+    R2Sym,
+};
+use crate::triangulation::utils::{self as tri_utils, Observation, Triangulation};
+use crate::utils;
+
+/// The tolerance of $1 - |d_i^\top d_j|$ below which two viewing rays are
+/// regarded as parallel.
+const PARALLEL_TOL: f64 = 1e-12;
+
+/// FracGM-based triangulation solver with linear (naive) relaxation.
+///
+/// The lifted variable is $[X; 1]$. The offset of the point from each viewing
+/// ray is divided by the distance of a reference point to the camera center,
+/// see [`tri_utils::compute_terms`], where the reference point is the initial
+/// guess. The noise bound is thus the expected angular reprojection error of
+/// an inlier observation in radians, e.g., the pixel noise divided by the
+/// focal length in pixels for pixel observations.
+pub struct LinearSolver {
+    /// The maximum number of iterations allowed.
+    pub max_iteration: usize,
+    /// The tolerance for convergence.
+    pub tol: f64,
+    /// The noise bound (sigma) for the Geman-McClure robust function (default: 0.1).
+    pub noise_bound: f64,
+    /// The value of $c$ defined in Geman-McClure robust function (default: 1.0).
+    pub c: f64,
+}
+
+impl LinearSolver {
+    pub fn new(max_iteration: usize, tol: f64, noise_bound: Option<f64>, c: Option<f64>) -> Self {
+        Self {
+            max_iteration,
+            tol,
+            noise_bound: noise_bound.unwrap_or(0.1),
+            c: c.unwrap_or(1.0),
+        }
+    }
+
+    /// Triangulate a point from its observations, and report the weight and
+    /// the reprojection error of each observation.
+    ///
+    /// # Arguments
+    ///
+    /// - `observations` - The observations of the point in at least two views.
+    ///
+    /// # Returns
+    ///
+    /// The triangulated point with the weights and the reprojection errors.
+    pub fn triangulate(&self, observations: &[Observation]) -> Triangulation {
+        self.check_input(&observations);
+
+        // The reference point is both the initial guess and the scale of the
+        // terms, so that it is computed only once.
+        let reference = self.compute_reference_point(observations);
+        let terms = tri_utils::compute_terms(observations, &reference, self.noise_bound, self.c());
+        let point = GemanMcclureSolver::solve_terms(self, terms, &reference);
+
+        let mut terms = tri_utils::compute_terms(observations, &point, self.noise_bound, self.c());
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(&point));
+
+        let reprojection_errors = observations
+            .iter()
+            .map(|observation| observation.reprojection_error(&point))
+            .collect();
+
+        Triangulation {
+            point,
+            weights: utils::compute_weights(&terms, self.c()),
+            reprojection_errors,
+        }
+    }
+
+    /// Triangulate the points of a batch of tracks, which are solved
+    /// independently on all the available CPU cores.
+    ///
+    /// # Arguments
+    ///
+    /// - `tracks` - The observations of each point.
+    ///
+    /// # Returns
+    ///
+    /// The triangulation of each track, in the order of `tracks`.
+    pub fn triangulate_tracks(&self, tracks: &[Vec<Observation>]) -> Vec<Triangulation> {
+        if tracks.is_empty() {
+            return Vec::new();
+        }
+
+        let chunk_size = tracks.len().div_ceil(num_cpus::get());
+
+        thread::scope(|scope| {
+            let handles = tracks
+                .chunks(chunk_size)
+                .map(|chunk| {
+                    scope.spawn(move || {
+                        chunk
+                            .iter()
+                            .map(|track| self.triangulate(track))
+                            .collect::<Vec<Triangulation>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            handles
+                .into_iter()
+                .flat_map(|handle| handle.join().unwrap())
+                .collect()
+        })
+    }
+
+    /// Classify the observations into inliers and outliers with the given
+    /// point, whose angular reprojection errors are compared with the noise
+    /// bound.
+    pub fn inliers(&self, observations: &[Observation], point: &Array1<f64>) -> Vec<bool> {
+        let mut terms = tri_utils::compute_terms(observations, point, self.noise_bound, self.c());
+        self.update_terms_cache(&mut terms, &self.mat_to_vec(point));
+
+        utils::compute_inliers(&terms, self.c())
+    }
+
+    /// The least squares point of a pair of viewing rays, where the pair with
+    /// the smallest Geman-McClure objective of the angular reprojection
+    /// errors over all the observations is chosen. Tracks are short, so that
+    /// the pairs are enumerated exhaustively instead of using the least
+    /// squares point of all the (possibly outlying) rays. Pairs of parallel
+    /// rays are skipped.
+    fn compute_reference_point(&self, observations: &[Observation]) -> Array1<f64> {
+        let rays = observations
+            .iter()
+            .map(|observation| observation.ray())
+            .collect::<Vec<(Array1<f64>, Array1<f64>)>>();
+        let mats = observations
+            .iter()
+            .map(|observation| {
+                let mat_n = observation.residual_mat();
+                mat_n.t().dot(&mat_n)
+            })
+            .collect::<Vec<Array2<f64>>>();
+
+        let c2 = self.c() * self.c();
+        let mut best: Option<(f64, Array1<f64>)> = None;
+        for i in 0..rays.len() {
+            for j in i + 1..rays.len() {
+                if rays[i].1.dot(&rays[j].1).abs() > 1.0 - PARALLEL_TOL {
+                    continue;
+                }
+
+                let point = self.vec_to_mat(&self.solve_x(&(&mats[i] + &mats[j])));
+
+                let cost = rays
+                    .iter()
+                    .map(|(center, dir)| {
+                        let r = tri_utils::compute_ray_sine(center, dir, &point) / self.noise_bound;
+                        c2 * r * r / (r * r + c2)
+                    })
+                    .sum::<f64>();
+                let better = match &best {
+                    Some((best_cost, _)) => cost < *best_cost,
+                    None => true,
+                };
+                if better {
+                    best = Some((cost, point));
+                }
+            }
+        }
+
+        let (_, point) = best.expect("Viewing rays must not be all parallel");
+
+        point
+    }
+}
+
+impl FractionalProgrammingMaterials<R2Sym> for LinearSolver {
+    type Input<'a> = &'a [Observation];
+    type Output = Array1<f64>;
+
+    fn dim(&self) -> usize {
+        4
+    }
+
+    fn max_iteration(&self) -> usize {
+        self.max_iteration
+    }
+    fn tol(&self) -> f64 {
+        self.tol
+    }
+    fn c(&self) -> f64 {
+        self.c
+    }
+
+    fn mat_to_vec(&self, point: &Array1<f64>) -> Array2<f64> {
+        let mut vec = Array2::<f64>::ones((self.dim(), 1));
+        vec.slice_mut(s![0..3, 0]).assign(point);
+
+        vec
+    }
+    fn vec_to_mat(&self, vec: &Array2<f64>) -> Array1<f64> {
+        vec.slice(s![0..3, 0]).to_owned()
+    }
+    fn project(&self, point: &Array1<f64>) -> Array1<f64> {
+        point.clone()
+    }
+
+    fn check_input(&self, observations: &Self::Input<'_>) {
+        tri_utils::check_observations(observations);
+    }
+
+    /// The terms whose offsets are scaled at the reference point, which is
+    /// also the initial guess. [`LinearSolver::triangulate`] computes the
+    /// reference point only once for both.
+    fn compute_terms(&self, observations: &Self::Input<'_>) -> Vec<Fractional<R2Sym>> {
+        let reference = self.compute_reference_point(observations);
+
+        tri_utils::compute_terms(observations, &reference, self.noise_bound, self.c())
+    }
+
+    /// The reference point of the terms, i.e., the least squares point of
+    /// the pair of viewing rays with the smallest Geman-McClure objective.
+    fn compute_initial_guess(&self, observations: &Self::Input<'_>) -> Array1<f64> {
+        self.compute_reference_point(observations)
+    }
+}
+
+impl GemanMcclureSolver<R2Sym> for LinearSolver {}
+impl GemanMcclureSolverDiagnostic<R2Sym> for LinearSolver {}
@@ -0,0 +1,201 @@
+// Copyright 2024 the FracGM authors. All rights reserved.
+// Use of this source code is governed by a BSD-style
+// license that can be found in the LICENSE file.
+
+use ndarray::prelude::*;
+use ndarray::Array2;
+use ndarray_linalg::Inverse;
+
+use crate::solver::{Fractional, R2Sym};
+
+/// The distance below which a point is regarded as coinciding with a camera
+/// center.
+const MIN_DISTANCE: f64 = 1e-12;
+
+/// An observation of a 3D point in a view.
+#[derive(Clone)]
+pub enum Observation {
+    /// A pixel $[u; v]$ of a camera with the 3x4 projection matrix $P$.
+    Pixel(Array2<f64>, Array1<f64>),
+    /// A bearing vector $v$ of a camera with the 4x4 (or 3x4) pose $[R | t]$,
+    /// which maps world points into the camera frame.
+    Bearing(Array2<f64>, Array1<f64>),
+}
+
+impl Observation {
+    /// Get the viewing ray of the observation in the world frame.
+    ///
+    /// # Returns
+    ///
+    /// The camera center and the unit direction of the ray.
+    pub fn ray(&self) -> (Array1<f64>, Array1<f64>) {
+        let (mat, last, dir) = match self {
+            // $P = [M | p_4]$, whose center is $-M^{-1} p_4$.
+            Observation::Pixel(projection, pixel) => {
+                let mat_inv = projection
+                    .slice(s![.., 0..3])
+                    .to_owned()
+                    .inv()
+                    .expect("Projection matrix must be of a finite camera");
+                let dir: Array1<f64> = mat_inv.dot(&array![pixel[0], pixel[1], 1.0]);
+                (mat_inv, projection.slice(s![.., 3]).to_owned(), dir)
+            }
+            Observation::Bearing(pose, bearing) => {
+                let rot_t = pose.slice(s![0..3, 0..3]).t().to_owned();
+                let dir: Array1<f64> = rot_t.dot(bearing);
+                (rot_t, pose.slice(s![0..3, 3]).to_owned(), dir)
+            }
+        };
+
+        let center: Array1<f64> = mat.dot(&last);
+        let norm = dir.dot(&dir).sqrt();
+
+        (center.mapv(|x| -x), dir / norm)
+    }
+
+    /// Computes the $3 \times 4$ matrix $N$ of the residual
+    /// $(I - d d^\top) (X - C)$ in the lifted variable $[X; 1]$, i.e., the
+    /// offset of the point $X$ from the viewing ray with center $C$ and unit
+    /// direction $d$.
+    pub fn residual_mat(&self) -> Array2<f64> {
+        let (center, dir) = self.ray();
+
+        let d_col = dir.view().into_shape((3, 1)).unwrap();
+        let proj = Array2::<f64>::eye(3) - d_col.dot(&d_col.t());
+
+        let mut mat = Array2::<f64>::zeros((3, 4));
+        mat.slice_mut(s![.., 0..3]).assign(&proj);
+        mat.slice_mut(s![.., 3])
+            .assign(&proj.dot(&center).mapv(|x| -x));
+
+        mat
+    }
+
+    /// Computes the reprojection error of a point, i.e., the distance in
+    /// pixels for pixel observations and the angle in radians for bearing
+    /// observations. Points on the principal plane of a camera have infinite
+    /// pixel errors.
+    pub fn reprojection_error(&self, point: &Array1<f64>) -> f64 {
+        match self {
+            Observation::Pixel(projection, pixel) => {
+                let x: Array1<f64> =
+                    projection.slice(s![.., 0..3]).dot(point) + projection.slice(s![.., 3]);
+                if x[2] == 0.0 {
+                    return f64::INFINITY;
+                }
+                let du = x[0] / x[2] - pixel[0];
+                let dv = x[1] / x[2] - pixel[1];
+                (du * du + dv * dv).sqrt()
+            }
+            Observation::Bearing(pose, bearing) => {
+                let x: Array1<f64> =
+                    pose.slice(s![0..3, 0..3]).dot(point) + pose.slice(s![0..3, 3]);
+                let cos = x.dot(bearing) / (x.dot(&x) * bearing.dot(bearing)).sqrt();
+                match cos.is_nan() {
+                    true => 0.0,
+                    false => cos.clamp(-1.0, 1.0).acos(),
+                }
+            }
+        }
+    }
+}
+
+/// Computes the sine of the angle between a viewing ray and the direction
+/// from its center to a point, i.e., the offset of the point from the ray
+/// divided by the distance of the point to the center.
+///
+/// # Arguments
+///
+/// - `center` - The center of the ray.
+/// - `dir` - The unit direction of the ray.
+/// - `point` - The point.
+pub fn compute_ray_sine(center: &Array1<f64>, dir: &Array1<f64>, point: &Array1<f64>) -> f64 {
+    let diff = point - center;
+    let offset = &diff - &(dir * dir.dot(&diff));
+
+    offset.dot(&offset).sqrt() / diff.dot(&diff).sqrt().max(MIN_DISTANCE)
+}
+
+/// The result of triangulating a point.
+#[derive(Clone)]
+pub struct Triangulation {
+    /// The triangulated point.
+    pub point: Array1<f64>,
+    /// The Geman-McClure weight of each observation, which is close to 1 for
+    /// inliers and vanishes for outliers.
+    pub weights: Vec<f64>,
+    /// The reprojection error of each observation, see
+    /// [`Observation::reprojection_error`].
+    pub reprojection_errors: Vec<f64>,
+}
+
+/// Checks if the observations are valid for triangulating a point.
+///
+/// # Panics
+///
+/// Panics if there are fewer than two observations, or an observation has a
+/// camera or a measurement of the wrong shape.
+pub fn check_observations(observations: &[Observation]) {
+    assert!(
+        observations.len() >= 2,
+        "At least 2 observations are required"
+    );
+
+    for observation in observations.iter() {
+        match observation {
+            Observation::Pixel(projection, pixel) => {
+                assert!(
+                    projection.dim() == (3, 4),
+                    "Projection matrices must be 3x4 matrices"
+                );
+                assert!(pixel.len() == 2, "Pixels must have 2 entries");
+            }
+            Observation::Bearing(pose, bearing) => {
+                assert!(
+                    pose.dim() == (3, 4) || pose.dim() == (4, 4),
+                    "Poses must be 3x4 or 4x4 matrices"
+                );
+                assert!(bearing.len() == 3, "Bearing vectors must have 3 entries");
+            }
+        }
+    }
+}
+
+/// Computes the Geman-McClure terms of the offsets of the point from the
+/// viewing rays, see [`Observation::residual_mat`], where the offset of each
+/// observation is divided by the distance of `reference` to the camera
+/// center. The residuals approximate the sines of the angular reprojection
+/// errors for points close to `reference`.
+///
+/// # Arguments
+///
+/// - `observations` - The observations of the point.
+/// - `reference` - The reference point, e.g., a rough estimate of the point.
+/// - `noise_bound` - The noise bound of the angular reprojection errors.
+/// - `c` - The value of $c$ defined in Geman-McClure robust function.
+///
+/// # Returns
+///
+/// The Geman-McClure term of each observation.
+pub fn compute_terms(
+    observations: &[Observation],
+    reference: &Array1<f64>,
+    noise_bound: f64,
+    c: f64,
+) -> Vec<Fractional<R2Sym>> {
+    let mut terms: Vec<Fractional<R2Sym>> = Vec::with_capacity(observations.len());
+
+    for observation in observations.iter() {
+        let mat_n = observation.residual_mat();
+
+        let (center, _) = observation.ray();
+        let diff = reference - &center;
+        let sigma = noise_bound * diff.dot(&diff).sqrt().max(MIN_DISTANCE);
+
+        let mat_m = mat_n.t().dot(&mat_n) / (sigma * sigma);
+
+        terms.push(Fractional::new(R2Sym::new(mat_m), c));
+    }
+
+    terms
+}
@@ -2,30 +2,143 @@
 // Use of this source code is governed by a BSD-style
 // license that can be found in the LICENSE file.
 
-use ndarray::arr1;
 use ndarray::Array1;
 use ndarray::Array2;
 use ndarray::Axis;
-use ndarray_linalg::Determinant;
-use ndarray_linalg::SVD;
+use ndarray_linalg::FactorizeInto;
+use ndarray_linalg::Solve;
 
+use crate::manifold::{self, Manifold};
+use crate::solver::{Fractional, R2};
+
+/// Projects a $d \times d$ matrix onto SO(d), e.g., a 3x3 matrix onto SO(3).
 pub fn project(mat: &Array2<f64>) -> Array2<f64> {
+    manifold::SpecialOrthogonal::new(mat.nrows()).project(mat)
+}
+
+/// Solves the homogeneous linear system $A y = e$, where $e$ is the last
+/// standard basis vector, and rescales the solution so that its last entry
+/// (the homogeneous coordinate) equals 1.
+pub fn solve_homogeneous(mat: &Array2<f64>) -> Array2<f64> {
     assert!(mat.dim().0 == mat.dim().1, "Matrix must be square");
-    assert!(mat.dim().0 == 3, "Matrix must be 3x3");
 
-    let (u, _, vt) = mat.svd(true, true).unwrap();
+    let dim = mat.dim().0;
+    let mut e = Array1::<f64>::zeros(dim);
+    e[dim - 1] = 1.0;
+
+    let lu_factor = mat.clone().factorize_into().unwrap();
 
-    let u = u.unwrap();
-    let vt = vt.unwrap();
+    let y = lu_factor
+        .solve_into(e)
+        .unwrap()
+        .into_shape((dim, 1))
+        .unwrap();
+    let schur = y[[dim - 1, 0]];
+
+    (1.0 / schur) * y
+}
+
+/// Solves the quadratic program $\min_x x^\top A x$ subject to the linear
+/// equality constraints $W^\top x = b$, whose solution is $x = A^{-1} W (W^\top
+/// A^{-1} W)^{-1} b$. It generalizes [`solve_homogeneous`], which is the case
+/// of $W = e$ and $b = 1$.
+///
+/// # Arguments
+///
+/// - `mat` - The matrix $A$.
+/// - `w` - The constraint matrix $W$ with shape `(dim, k)`.
+/// - `b` - The right-hand side $b$ with length `k`.
+///
+/// # Returns
+///
+/// The solution with shape `(dim, 1)`.
+pub fn solve_constrained(mat: &Array2<f64>, w: &Array2<f64>, b: &Array1<f64>) -> Array2<f64> {
+    assert!(mat.dim().0 == mat.dim().1, "Matrix must be square");
+    assert!(
+        w.dim().0 == mat.dim().0,
+        "Constraint matrix must have same rows"
+    );
+    assert!(
+        w.dim().1 == b.len(),
+        "Constraints and rhs must have same size"
+    );
 
-    let rot = u.dot(&vt);
+    let dim = mat.dim().0;
+    let lu_factor = mat.clone().factorize_into().unwrap();
 
-    if rot.det().unwrap() > 0.0 {
-        rot
-    } else {
-        let s = Array2::from_diag(&arr1(&[1.0, 1.0, -1.0]));
-        u.dot(&s).dot(&vt)
+    let mut y = Array2::<f64>::zeros((dim, w.dim().1));
+    for (mut y_col, w_col) in y.columns_mut().into_iter().zip(w.columns()) {
+        y_col.assign(&lu_factor.solve(&w_col).unwrap());
     }
+
+    let lambda = w.t().dot(&y).solve_into(b.clone()).unwrap();
+
+    y.dot(&lambda).into_shape((dim, 1)).unwrap()
+}
+
+/// Checks if two point clouds are paired correspondences with `n_cols` columns.
+///
+/// # Panics
+///
+/// Panics if the point clouds have different shapes or the number of columns
+/// is not `n_cols`.
+pub fn check_point_cloud_pair(pc1: &Array2<f64>, pc2: &Array2<f64>, n_cols: usize) {
+    assert!(
+        pc1.shape() == pc2.shape(),
+        "Input point clouds must have the same shape"
+    );
+    assert!(
+        pc1.shape()[1] == n_cols,
+        "Input point clouds must have {} columns",
+        n_cols
+    );
+}
+
+/// Classifies the Geman-McClure terms into inliers and outliers. A term is an
+/// inlier if its (normalized) residual is within $c$, i.e., its Geman-McClure
+/// weight $(c^2 / (c^2 + r^2))^2$ is at least $1/4$. Non-robust terms are
+/// always inliers.
+///
+/// # Arguments
+///
+/// - `terms` - The Geman-McClure terms, whose caches are evaluated at the
+///   solution.
+/// - `c` - The value of $c$ defined in Geman-McClure robust function.
+///
+/// # Returns
+///
+/// The inlier flag of each term.
+pub fn compute_inliers<R: R2>(terms: &[Fractional<R>], c: f64) -> Vec<bool> {
+    terms
+        .iter()
+        .map(|term| !term.is_robust() || term.r2() <= c * c)
+        .collect()
+}
+
+/// Computes the Geman-McClure weights $(c^2 / (c^2 + r^2))^2$ of the terms,
+/// which are close to 1 for inliers and vanish for outliers. The weights of
+/// non-robust terms are 1.
+///
+/// # Arguments
+///
+/// - `terms` - The Geman-McClure terms, whose caches are evaluated at the
+///   solution.
+/// - `c` - The value of $c$ defined in Geman-McClure robust function.
+///
+/// # Returns
+///
+/// The weight of each term.
+pub fn compute_weights<R: R2>(terms: &[Fractional<R>], c: f64) -> Vec<f64> {
+    terms
+        .iter()
+        .map(|term| {
+            if !term.is_robust() {
+                return 1.0;
+            }
+            let w = c * c / term.h();
+            w * w
+        })
+        .collect()
 }
 
 pub fn get_zero_mean_point_cloud(pc: &Array2<f64>) -> (Array2<f64>, Array1<f64>) {
@@ -42,7 +155,7 @@ pub fn get_zero_mean_point_cloud(pc: &Array2<f64>) -> (Array2<f64>, Array1<f64>)
 
 pub fn compute_complete_translation_invariant_measurements(pc: &Array2<f64>) -> Array2<f64> {
     let n_tims = pc.dim().0 * (pc.dim().0 - 1) / 2;
-    let mut tims = Array2::<f64>::zeros((n_tims, 3));
+    let mut tims = Array2::<f64>::zeros((n_tims, pc.dim().1));
 
     let mut idx = 0;
     for i in 0..pc.dim().0 {
@@ -58,7 +171,7 @@ pub fn compute_complete_translation_invariant_measurements(pc: &Array2<f64>) ->
 
 pub fn compute_chain_translation_invariant_measurements(pc: &Array2<f64>) -> Array2<f64> {
     let n_tims = pc.dim().0;
-    let mut tims = Array2::<f64>::zeros((n_tims, 3));
+    let mut tims = Array2::<f64>::zeros((n_tims, pc.dim().1));
 
     for i in 0..pc.dim().0 {
         let j = if i == pc.dim().0 - 1 { 0 } else { i + 1 };